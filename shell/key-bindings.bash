@@ -0,0 +1,15 @@
+# Insert a binary picked with sb at the cursor: Ctrl-X Ctrl-B
+__sb_widget() {
+    local selected
+    selected="$(sb --tty)" || return
+
+    [ -n "$selected" ] || return
+    selected="${selected//$'\n'/ }"
+
+    READLINE_LINE="${READLINE_LINE:0:$READLINE_POINT}$selected${READLINE_LINE:$READLINE_POINT}"
+    READLINE_POINT=$(( READLINE_POINT + ${#selected} ))
+}
+
+bind -m emacs-standard -x '"\C-x\C-b": __sb_widget'
+bind -m vi-command -x '"\C-x\C-b": __sb_widget'
+bind -m vi-insert -x '"\C-x\C-b": __sb_widget'
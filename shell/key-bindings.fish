@@ -0,0 +1,16 @@
+# Insert a binary picked with sb at the cursor: Ctrl-X Ctrl-B
+function sb-widget
+    set -l selected (sb --tty)
+
+    if test -n "$selected"
+        commandline -i -- (string join ' ' $selected)
+    end
+
+    commandline -f repaint
+end
+
+bind \cx\cb sb-widget
+
+if bind -M insert >/dev/null 2>&1
+    bind -M insert \cx\cb sb-widget
+end
@@ -1,10 +1,28 @@
-use color_eyre::eyre::Result as RepResult;
+use std::env;
+
+use color_eyre::eyre::{Result as RepResult, eyre};
 
 use crate::{
-    binaries::{init_search_path, is_binary_exist},
-    cli::{AppMode, Cli},
+    binaries::{
+        SearchLimits,
+        init_alias_grouping,
+        init_search_limits,
+        init_search_path,
+    },
+    cli::{AppMode, Cli, Command},
+    config::print_config,
+    desktop::init_desktop_apps,
+    frecency::init_frecency_weight,
+    history::get_last_query,
+    init::print_init_script,
+    not_found::{get_not_found_limits, run_not_found},
+    query::init_default_scoring,
+    root::init_root_dir,
+    shell::init_shell_entries_source,
+    toolchains::init_toolchains,
     simple_app::SimpleApp,
     interactive_app::InteractiveApp,
+    daemon_app::DaemonApp,
 };
 
 pub trait App {
@@ -12,15 +30,86 @@ pub trait App {
     fn run(&mut self) -> RepResult<()>;
 }
 
+const SIMPLE_RESULT_LENGTH: usize = 10;
+
+pub fn is_tool_available(name: &str) -> bool {
+    let path = env::var_os("PATH").unwrap_or_default();
+
+    env::split_paths(&path)
+        .any(|dir| dir.join(name).is_file())
+}
+
+fn run_command(command: &Command) -> RepResult<()> {
+    match command {
+        Command::Init { shell } => print_init_script(*shell),
+        Command::NotFound { command, args } => return run_not_found(command, args),
+        Command::Config { print_default } => print_config(*print_default),
+    }
+
+    Ok(())
+}
+
 pub fn run_app(mut args: Cli) -> RepResult<()> {
+    if args.last_query {
+        let query = get_last_query().ok_or(eyre!("no previous query in history"))?;
+
+        args.input = Some(query);
+        args.app_mode.simple = true;
+    }
+
     let app_mode = AppMode::from(&(&args).app_mode);
 
+    let length = match app_mode {
+        AppMode::Simple => args.result_length.or(Some(SIMPLE_RESULT_LENGTH)),
+        AppMode::Interactive | AppMode::Daemon => args.result_length,
+    };
+
+    args.result_length = length;
+
+    let limits = match &args.command {
+        Some(Command::NotFound { .. }) => get_not_found_limits(),
+        _ => SearchLimits {
+            min_score: args.min_score,
+            length,
+        },
+    };
+
+    init_search_limits(limits);
+
+    init_root_dir(args.root_dir.clone());
+
     if let Some(path) = &args.search_path {
         init_search_path(&path)
     }
 
+    init_frecency_weight(args.frecency_weight);
+
+    if let Some(scoring) = args.matcher {
+        init_default_scoring(scoring);
+    }
+
+    init_alias_grouping(!args.expand_aliases);
+    init_desktop_apps(args.desktop_apps);
+    // Toolchain dirs live under the host's home, not the root's.
+    init_toolchains(args.toolchains && args.root_dir.is_none());
+
+    // Shell entries come from the host's shell, not the root's.
+    if let Some(source) = &args.shell_entries
+    && args.root_dir.is_none()
+    {
+        init_shell_entries_source(source);
+    }
+
+    if let Some(command) = &args.command {
+        return run_command(command);
+    }
+
+    if app_mode == AppMode::Daemon {
+        args.show_descriptions = true;
+    }
+
     if args.show_descriptions
-    && (!is_binary_exist("man") || !is_binary_exist("groff"))
+    && (!is_tool_available("man") || !is_tool_available("groff"))
     {
         args.show_descriptions = false;
     }
@@ -28,5 +117,6 @@ pub fn run_app(mut args: Cli) -> RepResult<()> {
     match app_mode {
         AppMode::Simple => SimpleApp::with_args(args).run(),
         AppMode::Interactive => InteractiveApp::with_args(args).run(),
+        AppMode::Daemon => DaemonApp::with_args(args).run(),
     }
 }
@@ -1,34 +1,144 @@
 use std::{
+    cmp::Reverse,
     env,
     fs::{self, DirEntry},
-    path::PathBuf,
+    path::{Path, PathBuf},
     ops::{Deref, DerefMut},
     os::unix::fs::PermissionsExt,
-    sync::{Arc, OnceLock, RwLock},
+    sync::{Arc, LazyLock, OnceLock, RwLock},
     collections::{BTreeMap, HashMap},
 };
 
 use ordered_float::OrderedFloat;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use strsim::jaro_winkler;
 
 use crate::{
     descriptions::{
         Description,
+        DescriptionProvider,
         get_description,
+        get_description_providers,
         insert_description,
+        remove_description,
     },
+    desktop::{DesktopEntry, get_desktop_apps},
+    details::{BinaryDetails, detect_details},
+    filetype::{FileInfo, FileType, detect_file_info, detect_file_type},
+    frecency::blend_frecency,
     man::get_manpaths,
+    packages::{Package, get_package_owner},
+    query::Query,
+    root::{canonicalize, get_root_dir, metadata, rooted},
     roff::extract_description_section,
+    shell::get_shell_entries,
+    toolchains::{get_path_hint, get_toolchain_dirs},
 };
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum BinaryKind {
+    Executable,
+    Alias,
+    Function,
+    Builtin,
+}
+
+impl BinaryKind {
+    pub fn marker(&self) -> Option<&'static str> {
+        match self {
+            BinaryKind::Executable => None,
+            BinaryKind::Alias => Some("alias"),
+            BinaryKind::Function => Some("function"),
+            BinaryKind::Builtin => Some("builtin"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Binary {
     pub name: String,
+    pub path: PathBuf,
+    pub kind: BinaryKind,
+    pub definition: Option<String>,
+    pub apps: Vec<DesktopEntry>,
+    pub aliases: Vec<String>,
+    pub multi_call: Option<String>,
+    pub off_path: bool,
+    pub file_info: Option<FileInfo>,
+    file_type: OnceLock<FileType>,
+    pub details: Option<BinaryDetails>,
     pub manpath: Option<PathBuf>,
 }
 
 impl Binary {
+    pub fn new(name: String, path: PathBuf, kind: BinaryKind) -> Self {
+        Self {
+            name,
+            path,
+            kind,
+            definition: None,
+            apps: vec![],
+            aliases: vec![],
+            multi_call: None,
+            off_path: false,
+            file_info: None,
+            file_type: OnceLock::new(),
+            details: None,
+            manpath: None,
+        }
+    }
+
+    pub fn get_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str())
+            .chain(self.aliases.iter().map(|alias| alias.as_str()))
+    }
+
+    pub fn get_file_type(&self) -> Option<FileType> {
+        if self.kind != BinaryKind::Executable {
+            return None;
+        }
+
+        // Type filters ask on every keystroke, read the headers once.
+        let file_type = self.file_type.get_or_init(|| match &self.file_info {
+            Some(info) => info.file_type.clone(),
+            None => detect_file_type(&self.path),
+        });
+
+        Some(file_type.clone())
+    }
+
+    pub fn get_package(&self) -> Option<Arc<Package>> {
+        if self.kind != BinaryKind::Executable {
+            return None;
+        }
+
+        match &self.file_info {
+            Some(info) => info.package.clone(),
+            None => get_package_owner(&self.path),
+        }
+    }
+
+    pub fn get_marker(&self) -> Option<&'static str> {
+        match self.kind.marker() {
+            Some(marker) => Some(marker),
+            None if self.off_path => Some(OFF_PATH_MARKER),
+            None => None,
+        }
+    }
+
+    pub fn get_path_hint(&self) -> Option<String> {
+        match self.off_path {
+            true => get_path_hint(&self.path),
+            false => None,
+        }
+    }
+
+    pub fn get_key(&self) -> String {
+        match self.kind.marker() {
+            Some(marker) => format!("{}:{}", marker, self.name),
+            None => self.name.clone(),
+        }
+    }
+
     pub fn extract_description(&self) -> Option<Description> {
         let path = match self.manpath.as_ref() {
             Some(p) => p,
@@ -42,39 +152,115 @@ impl Binary {
             .and_then(|section| section.get_description())
     }
 
-    pub fn get_description(&self) -> Option<Arc<Description>>{
+    fn get_man_description(&self) -> Option<Arc<Description>> {
         if let Some(description) = get_description(self) {
             return Some(description)
         }
 
         let description = self
             .extract_description()
-            .map(|desc| Arc::new(desc));
+            .map(Arc::new)?;
+
+        insert_description(self, description.clone());
+        Some(description)
+    }
 
-        if let Some(desc) = &description {
-            insert_description(self, desc.clone());
+    fn get_provided_description(&self, provider: DescriptionProvider) -> Option<Arc<Description>> {
+        match provider {
+            DescriptionProvider::Shell => self.definition
+                .as_ref()
+                .map(|definition| Arc::new(Description::from(definition.clone()))),
+            DescriptionProvider::Man => self.get_man_description(),
+            DescriptionProvider::Desktop => self.apps
+                .iter()
+                .find_map(|app| app.comment.as_ref().or(app.generic_name.as_ref()))
+                .map(|comment| Arc::new(Description::from(comment.clone()))),
         }
+    }
 
-        description
+    pub fn get_description(&self) -> Option<Arc<Description>> {
+        get_description_providers()
+            .iter()
+            .find_map(|provider| self.get_provided_description(*provider))
     }
 }
 
 pub type BinaryNode = Arc<RwLock<Binary>>;
 pub type Binaries = HashMap<String, BinaryNode>;
 
+pub struct SearchLimits {
+    pub min_score: f64,
+    pub length: Option<usize>,
+}
+
+const DEFAULT_ROOT_PATH: &str = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
+pub const OFF_PATH_MARKER: &str = "off-PATH";
+const MULTI_CALL_BINARIES: [&str; 3] = ["busybox", "toybox", "coreutils"];
+
 static SEARCH_PATHS: OnceLock<Vec<PathBuf>> = OnceLock::new();
-static BINARIES: OnceLock<Binaries> = OnceLock::new();
+static GROUP_ALIASES: OnceLock<bool> = OnceLock::new();
+static SEARCH_LIMITS: OnceLock<SearchLimits> = OnceLock::new();
+static BINARIES: LazyLock<RwLock<Arc<Binaries>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(init_binaries())));
 
 pub fn init_search_path(path: &String) {
     SEARCH_PATHS.get_or_init(|| {
         env::split_paths(&path)
+            .map(|path| rooted(&path))
             .collect::<Vec<PathBuf>>()
     });
 }
 
+pub fn init_alias_grouping(enabled: bool) {
+    GROUP_ALIASES.get_or_init(|| enabled);
+}
+
+pub fn init_search_limits(limits: SearchLimits) {
+    SEARCH_LIMITS.get_or_init(|| limits);
+}
+
+fn add_dir_binaries(binaries: &mut Binaries, paths: &[PathBuf], off_path: bool) {
+    let dirs = paths
+        .iter()
+        .flat_map(fs::read_dir);
+
+    let entries = dirs.flatten();
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        if !is_entry_executable(&entry) {
+            continue;
+        }
+
+        let name = entry
+            .file_name()
+            .to_string_lossy()
+            .to_string();
+
+        binaries
+            .entry(name.clone())
+            .or_insert_with(|| {
+                let mut binary = Binary::new(name, entry.path(), BinaryKind::Executable);
+                binary.off_path = off_path;
+
+                Arc::new(RwLock::new(binary))
+            });
+    }
+}
+
 fn init_binaries() -> Binaries {
     let mut binaries = Binaries::new();
 
+    if SEARCH_PATHS.get().is_none()
+    && get_root_dir().is_some()
+    {
+        init_search_path(&DEFAULT_ROOT_PATH.to_owned());
+    }
+
     if SEARCH_PATHS.get().is_none()
     && let Some(v) = env::var_os("PATH")
     {
@@ -87,49 +273,176 @@ fn init_binaries() -> Binaries {
 
     let paths = SEARCH_PATHS.get().unwrap();
 
-    let dirs = paths
-        .into_iter()
-        .map(|path| fs::read_dir(&path))
-        .flatten();
+    add_dir_binaries(&mut binaries, paths, false);
+    add_dir_binaries(&mut binaries, &get_toolchain_dirs(paths), true);
 
-    let entries = dirs
-        .map(|dir| dir.into_iter())
-        .flatten();
+    for entry in get_shell_entries() {
+        binaries
+            .entry(entry.get_key())
+            .or_insert_with(|| Arc::new(RwLock::new(entry)));
+    }
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(v) => v,
-            Err(_) => continue,
+    for app in get_desktop_apps() {
+        if let Some(binary) = binaries.get(&app.exec_name) {
+            binary.write().unwrap().apps.push(app.entry);
+            continue;
+        }
+
+        let path = match app.exec_path {
+            Some(v) if is_path_executable(&v) => v,
+            _ => continue,
         };
 
-        if !is_entry_executable(&entry) {
+        let mut binary = Binary::new(app.exec_name.clone(), path, BinaryKind::Executable);
+        binary.apps.push(app.entry);
+
+        binaries.insert(app.exec_name, Arc::new(RwLock::new(binary)));
+    }
+
+    group_symlink_aliases(&mut binaries);
+
+    binaries
+}
+
+fn get_primary_order(name: &str, target_name: &str) -> (u8, usize, String) {
+    let is_variant_base = target_name
+        .strip_prefix(name)
+        .is_some_and(|rest| rest.starts_with('.'));
+
+    let rank = match name {
+        _ if is_variant_base => 0,
+        _ if name == target_name => 1,
+        _ => 2,
+    };
+
+    (rank, name.len(), name.to_owned())
+}
+
+fn group_symlink_aliases(binaries: &mut Binaries) {
+    let mut groups = HashMap::<PathBuf, Vec<String>>::new();
+
+    for (key, binary) in binaries.iter() {
+        let readable_binary = binary.read().unwrap();
+
+        if readable_binary.kind != BinaryKind::Executable {
             continue;
         }
 
-        let name = entry
-            .file_name()
-            .to_string_lossy()
-            .to_string();
+        if let Ok(target) = canonicalize(&readable_binary.path) {
+            groups.entry(target).or_default().push(key.clone());
+        }
+    }
+
+    let group_aliases = GROUP_ALIASES.get().copied().unwrap_or(true);
+
+    for (target, mut names) in groups {
+        let target_name = match target.file_name() {
+            Some(v) => v.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        if MULTI_CALL_BINARIES.contains(&target_name.as_str()) {
+            for name in names.iter().filter(|name| **name != target_name) {
+                binaries[name].write().unwrap().multi_call = Some(target_name.clone());
+            }
+            continue;
+        }
+
+        if !group_aliases || names.len() < 2 {
+            continue;
+        }
+
+        names.sort_by_key(|name| get_primary_order(name, &target_name));
+
+        let primary = names.remove(0);
+        let mut aliases = Vec::with_capacity(names.len());
+        let mut apps = Vec::new();
 
-        binaries.insert(
-            name.clone(),
-            Arc::new(RwLock::new(Binary {
-                name: name.clone(),
-                manpath: None,
-            })),
-        );
+        for name in names {
+            if let Some(alias) = binaries.remove(&name) {
+                apps.append(&mut alias.write().unwrap().apps);
+                aliases.push(name);
+            }
+        }
+
+        let mut writeable_binary = binaries[&primary].write().unwrap();
+        writeable_binary.aliases = aliases;
+        writeable_binary.apps.append(&mut apps);
     }
+}
 
-    binaries
+pub fn load_binaries() {
+    LazyLock::force(&BINARIES);
+}
+
+fn get_binaries() -> Arc<Binaries> {
+    BINARIES.read().unwrap().clone()
 }
 
-pub fn is_binary_exist(name: &str) -> bool {
-    let binaries = BINARIES.get_or_init(init_binaries);
-    binaries.contains_key(name)
+pub fn get_indexed_count() -> usize {
+    get_binaries().len()
+}
+
+pub fn reload_binaries() {
+    let binaries = init_binaries();
+    let old_binaries = get_binaries();
+
+    for (key, old_binary) in old_binaries.iter() {
+        let is_changed = match binaries.get(key) {
+            Some(binary) => binary.read().unwrap().path != old_binary.read().unwrap().path,
+            None => true,
+        };
+
+        if is_changed {
+            remove_description(&old_binary.read().unwrap());
+        }
+    }
+
+    *BINARIES.write().unwrap() = Arc::new(binaries);
+}
+
+pub fn get_shadowed_paths(binary: &Binary) -> Vec<PathBuf> {
+    let paths = match SEARCH_PATHS.get() {
+        Some(v) if binary.kind == BinaryKind::Executable => v,
+        _ => return vec![],
+    };
+
+    let mut shadowed = vec![];
+
+    for dir in paths.iter().cloned().chain(get_toolchain_dirs(paths)) {
+        let path = dir.join(&binary.name);
+
+        if path != binary.path
+        && !shadowed.contains(&path)
+        && is_path_executable(&path)
+        {
+            shadowed.push(path);
+        }
+    }
+
+    shadowed
+}
+
+pub fn get_watch_dirs() -> Vec<PathBuf> {
+    let paths = match SEARCH_PATHS.get() {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    paths
+        .iter()
+        .cloned()
+        .chain(get_toolchain_dirs(paths))
+        .filter(|dir| dir.is_dir())
+        .collect()
 }
 
 fn is_entry_executable(entry: &DirEntry) -> bool {
-    let metadata = match fs::metadata(entry.path()) {
+    is_path_executable(&entry.path())
+}
+
+fn is_path_executable(path: &Path) -> bool {
+    let metadata = match metadata(path) {
         Ok(md) => md,
         Err(_) => return false,
     };
@@ -144,12 +457,15 @@ fn is_entry_executable(entry: &DirEntry) -> bool {
     mode & x_perm != 0
 }
 
+pub type BinSearchKey = (OrderedFloat<f64>, Reverse<String>, Reverse<BinaryKind>);
+
 type BinSearchResultInner =
-    BTreeMap<OrderedFloat<f64>, BinaryNode>;
+    BTreeMap<BinSearchKey, BinaryNode>;
 
 #[derive(Default, Clone)]
 pub struct BinSearchResult {
     inner: BinSearchResultInner,
+    pub total: usize,
 }
 
 impl Deref for BinSearchResult {
@@ -180,22 +496,62 @@ impl BinSearchResult {
     {
         self.values().rev().map(|v| v.clone())
     }
-}
 
-pub fn search_binaries(search: &str) -> BinSearchResult {
-    let binaries = BINARIES.get_or_init(init_binaries);
+    pub fn is_truncated(&self) -> bool {
+        self.inner.len() < self.total
+    }
 
-    let get_similarity = |val: &str| jaro_winkler(val, search);
+    pub fn truncate(&mut self, length: usize) {
+        while self.inner.len() > length {
+            self.inner.pop_first();
+        }
+    }
+}
+
+fn search_nodes<'a, I>(
+    nodes: I,
+    query: &Query,
+    is_cancelled: &(dyn Fn() -> bool + Sync),
+) -> Option<BinSearchResult>
+where
+    I: ParallelIterator<Item = &'a BinaryNode>,
+{
+    let limits = SEARCH_LIMITS.get();
+
+    let min_score = limits
+        .map(|l| l.min_score)
+        .unwrap_or(0.0);
+
+    let mut result = nodes
+        .filter_map(|binary| {
+            if is_cancelled() {
+                return None;
+            }
 
-    binaries
-        .par_iter()
-        .map(|(_, binary)| {
             let readable_binary = binary.read().unwrap();
 
-            let similarity = get_similarity(&readable_binary.name.as_str());
-            let key = OrderedFloat(similarity);
+            if !query.matches(&readable_binary) {
+                return None;
+            }
+
+            let name = &readable_binary.name;
+            let similarity = query.similarity(&readable_binary);
+
+            if similarity < min_score {
+                return None;
+            }
 
-            (key, binary)
+            let score = readable_binary
+                .get_names()
+                .map(|name| blend_frecency(name, similarity))
+                .fold(0.0, f64::max);
+            let key = (
+                OrderedFloat(score),
+                Reverse(name.clone()),
+                Reverse(readable_binary.kind),
+            );
+
+            Some((key, binary))
         })
         .fold_with(BinSearchResult::default(), |mut acc, (k, v)| {
             acc.insert(k, v.clone());
@@ -203,19 +559,68 @@ pub fn search_binaries(search: &str) -> BinSearchResult {
         })
         .reduce_with(|mut acc1, acc2| {
             for (k, v) in acc2.iter() {
-                acc1.entry(*k).or_insert(v.clone());
+                acc1.entry(k.clone()).or_insert(v.clone());
             }
             acc1
         })
-        .unwrap_or_else(BinSearchResult::default)
+        .unwrap_or_else(BinSearchResult::default);
+
+    if is_cancelled() {
+        return None;
+    }
+
+    result.total = result.len();
+
+    if let Some(length) = limits.and_then(|l| l.length) {
+        result.truncate(length);
+    }
+
+    Some(result)
+}
+
+pub fn search_binaries(query: &Query) -> BinSearchResult {
+    search_binaries_cancellable(query, None, &|| false)
+        .unwrap_or_default()
+}
+
+pub fn search_binaries_cancellable(
+    query: &Query,
+    base: Option<&BinSearchResult>,
+    is_cancelled: &(dyn Fn() -> bool + Sync),
+) -> Option<BinSearchResult> {
+    if let Some(base) = base {
+        let nodes = base
+            .par_iter()
+            .map(|(_, binary)| binary);
+
+        return search_nodes(nodes, query, is_cancelled);
+    }
+
+    let binaries = get_binaries();
+    let nodes = binaries
+        .par_iter()
+        .map(|(_, binary)| binary);
+
+    search_nodes(nodes, query, is_cancelled)
 }
 
-pub fn attach_manpaths(binaries: &Vec<BinaryNode>) {
+pub fn attach_manpaths(binaries: &[BinaryNode]) {
+    if !get_description_providers().contains(&DescriptionProvider::Man) {
+        return;
+    }
+
     let attachables = binaries
         .iter()
-        .filter(|binary| binary.read().unwrap().manpath.is_none());
+        .filter(|binary| {
+            let readable_binary = binary.read().unwrap();
+
+            readable_binary.kind == BinaryKind::Executable
+            && readable_binary.manpath.is_none()
+        })
+        .collect::<Vec<&BinaryNode>>();
 
-    let names = attachables.clone()
+    let names = attachables
+        .iter()
         .map(|binary| binary.read().unwrap().name.clone())
         .collect::<Vec<String>>();
 
@@ -223,25 +628,61 @@ pub fn attach_manpaths(binaries: &Vec<BinaryNode>) {
         return;
     }
 
-    let manpaths = match get_manpaths(names) {
+    let manpaths = match get_manpaths(names.clone()) {
         Some(v) => v,
         None => return,
     };
 
-    let mut paths = manpaths.paths.iter().peekable();
+    let mut paths = manpaths.paths.into_iter();
     let not_founds = manpaths.not_founds;
 
+    let manpaths_by_name = names
+        .into_iter()
+        .filter(|name| !not_founds.contains(name))
+        .filter_map(|name| Some((name, PathBuf::from(paths.next()?))))
+        .collect::<HashMap<String, PathBuf>>();
+
     for attachable in attachables {
         let mut writeable_binary = attachable.write().unwrap();
-        let name = &writeable_binary.name;
+        let manpath = manpaths_by_name.get(&writeable_binary.name).cloned();
+
+        writeable_binary.manpath = manpath;
+    }
+}
 
-        if not_founds.contains(&name) {
-            writeable_binary.manpath = None;
-        } else {
-            let manpath = paths.next().map(PathBuf::from);
-            writeable_binary.manpath = manpath;
+pub fn attach_file_infos(binaries: &[BinaryNode]) {
+    for binary in binaries {
+        let readable_binary = binary.read().unwrap();
+
+        if readable_binary.kind != BinaryKind::Executable
+        || readable_binary.file_info.is_some()
+        {
+            continue;
+        }
+
+        let path = readable_binary.path.clone();
+        drop(readable_binary);
+
+        let file_info = detect_file_info(&path);
+        binary.write().unwrap().file_info = Some(file_info);
+    }
+}
+
+pub fn attach_details(binaries: &[BinaryNode]) {
+    attach_manpaths(binaries);
+
+    for binary in binaries {
+        let readable_binary = binary.read().unwrap();
+
+        if readable_binary.kind != BinaryKind::Executable
+        || readable_binary.details.is_some()
+        {
+            continue;
         }
 
-        drop(writeable_binary);
+        let details = detect_details(&readable_binary);
+        drop(readable_binary);
+
+        binary.write().unwrap().details = details;
     }
 }
@@ -1,4 +1,8 @@
-use clap::{Args, Parser};
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+
+use crate::query::Scoring;
 
 #[derive(Default, Parser, Debug)]
 #[command(
@@ -7,6 +11,10 @@ use clap::{Args, Parser};
     next_line_help = true,
     disable_help_flag = true,
     disable_version_flag = true,
+    disable_help_subcommand = true,
+    args_conflicts_with_subcommands = true,
+    group = ArgGroup::new("search_input").args(["input", "last_query"]),
+    group = ArgGroup::new("simple_ui_mode").args(["simple", "last_query"]).multiple(true),
 )]
 pub struct Cli {
     /// Print help.
@@ -25,9 +33,42 @@ pub struct Cli {
     )]
     _version: (),
     
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     #[command(flatten)]
     pub app_mode: AppModeArg,
 
+    /// Draw the interactive app on /dev/tty and print
+    /// the accepted binary name to stdout.
+    /// Used by the shell widgets of "sb init".
+    #[arg(
+        long = "tty",
+        verbatim_doc_comment,
+    )]
+    pub use_tty: bool,
+
+    /// Draw the interactive app inline below the prompt
+    /// with the given height in lines or a percentage
+    /// of the terminal, e.g. 15 or 40%, instead of
+    /// taking over the whole screen.
+    #[arg(
+        long = "height",
+        value_name = "N[%]",
+        value_parser = parse_height,
+        verbatim_doc_comment,
+    )]
+    pub height: Option<Height>,
+
+    /// Use the full terminal width in the interactive app
+    /// instead of a centered 80-column layout.
+    #[arg(
+        short = 'w',
+        long = "full-width",
+        verbatim_doc_comment,
+    )]
+    pub full_width: bool,
+
     /// Copy the first binary name
     /// in the search result to the clipboard.
     /// Input must be given!
@@ -48,16 +89,53 @@ pub struct Cli {
     )]
     pub show_descriptions: bool,
 
-    /// Set the length of binary names to display in the result.
+    /// Print the result as JSON with file type details.
     /// Simple app mode must be enabled!
+    #[arg(
+        short = 'j',
+        long = "json",
+        requires = "simple_ui_mode",
+        verbatim_doc_comment,
+    )]
+    pub json_output: bool,
+
+    /// Set the maximum count of binaries in the result.
+    /// Default is 10 in simple app mode, unlimited otherwise.
     #[arg(
         short = 'l',
         long = "length",
-        requires = "simple_ui_mode",
-        default_value_t = 10,
         verbatim_doc_comment,
     )]
-    pub result_length: usize,
+    pub result_length: Option<usize>,
+
+    /// Set the minimum similarity score (0.0 - 1.0)
+    /// for a binary to be included in the result.
+    #[arg(
+        short = 'm',
+        long = "min-score",
+        value_name = "SCORE",
+        default_value_t = 0.6,
+        verbatim_doc_comment,
+    )]
+    pub min_score: f64,
+
+    /// Set how names are scored against the input.
+    /// "typo" ranks by edit distance, for mistyped names.
+    #[arg(
+        long = "matcher",
+        value_enum,
+        verbatim_doc_comment,
+    )]
+    pub matcher: Option<Scoring>,
+
+    /// Narrow the previous result instead of searching
+    /// all binaries again while the input only grows.
+    /// Interactive app mode only, faster but less accurate.
+    #[arg(
+        long = "incremental",
+        verbatim_doc_comment,
+    )]
+    pub incremental: bool,
 
     /// Set the path variable for searching binaries in it.
     /// Default value depends on $PATH environment variable.
@@ -68,14 +146,180 @@ pub struct Cli {
     )]
     pub search_path: Option<String>,
 
+    /// Search a different root filesystem, such as a chroot,
+    /// an extracted container image or a sysroot.
+    /// Search paths, man pages and desktop applications
+    /// resolve relative to it. Shell entries and toolchains
+    /// of the host are ignored.
+    #[arg(
+        short = 'r',
+        long = "root",
+        value_name = "DIR",
+        verbatim_doc_comment,
+    )]
+    pub root_dir: Option<PathBuf>,
+
+    /// Set the weight (0.0 - 1.0) of usage frequency in the ranking.
+    /// Usage is read from shell histories and past picks.
+    /// Zero disables it.
+    #[arg(
+        short = 'f',
+        long = "frecency",
+        value_name = "WEIGHT",
+        value_parser = parse_weight,
+        default_value_t = 0.0,
+        verbatim_doc_comment,
+    )]
+    pub frecency_weight: f64,
+
+    /// List names that are symlinks to the same binary
+    /// separately instead of grouping them under one entry.
+    #[arg(
+        short = 'e',
+        long = "expand-aliases",
+        verbatim_doc_comment,
+    )]
+    pub expand_aliases: bool,
+
+    /// Include shell aliases, functions and builtins in the search.
+    /// Reads the output of "alias", "declare -f" (or "functions")
+    /// and "enable" from FILE, from stdin if FILE is "-", or from
+    /// $XDG_STATE_HOME/sb/shell if FILE is not given.
+    /// Ignored with --root.
+    #[arg(
+        short = 'S',
+        long = "shell-entries",
+        value_name = "FILE",
+        num_args = 0..=1,
+        default_missing_value = "",
+        verbatim_doc_comment,
+    )]
+    pub shell_entries: Option<String>,
+
+    /// Include desktop applications in the search.
+    /// Their names, comments and keywords are matched
+    /// against the input to find the binaries they run.
+    #[arg(
+        short = 'a',
+        long = "apps",
+        verbatim_doc_comment,
+    )]
+    pub desktop_apps: bool,
+
+    /// Also search well-known toolchain bin directories
+    /// (cargo, go, pipx, npm, bun, deno, nix) that are not
+    /// on PATH. Such binaries are marked as off-PATH.
+    /// Ignored with --root.
+    #[arg(
+        short = 't',
+        long = "toolchains",
+        verbatim_doc_comment,
+    )]
+    pub toolchains: bool,
+
+    /// Search the last query of the history again
+    /// in simple app mode. Queries and accepted binaries
+    /// are kept in $XDG_STATE_HOME/sb/history.
+    #[arg(
+        long = "last",
+        verbatim_doc_comment,
+    )]
+    pub last_query: bool,
+
     /// Name input for searching binaries with similar name.
+    /// Space-separated terms, "exact" terms, -exclusions and
+    /// dir:<DIR>, has:man, section:<N>, pkg:<PACKAGE>,
+    /// type:<elf|static|dynamic|<arch>|script|<interpreter>|
+    ///       appimage|flatpak|symlink|multicall|busybox|
+    ///       offpath|alias|function|builtin>
+    /// filters are supported. Use "--" before an input
+    /// that is also a subcommand name, e.g. sb -- init.
     #[arg(
-        group = "search_input",
         value_name = "SEARCH_INPUT",
+        verbatim_doc_comment,
     )]
     pub input: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum Height {
+    Lines(u16),
+    Percent(u16),
+}
+
+pub fn parse_height(value: &str) -> Result<Height, String> {
+    let (number, is_percent) = match value.strip_suffix('%') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+
+    let number = number
+        .parse::<u16>()
+        .map_err(|_| format!("invalid height: {}", value))?;
+
+    match is_percent {
+        true if number == 0 || number > 100 => Err("percentage must be between 1 and 100".to_owned()),
+        true => Ok(Height::Percent(number)),
+        false if number == 0 => Err("height must be positive".to_owned()),
+        false => Ok(Height::Lines(number)),
+    }
+}
+
+pub fn check_weight(weight: f64) -> Result<f64, String> {
+    match weight {
+        v if (0.0..=1.0).contains(&v) => Ok(v),
+        _ => Err("weight must be between 0.0 and 1.0".to_owned()),
+    }
+}
+
+pub fn parse_weight(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .map_err(|_| format!("invalid weight: {}", value))
+        .and_then(check_weight)
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print a key-binding widget for the shell.
+    /// It opens the interactive app with Ctrl-X Ctrl-B
+    /// and inserts the accepted binary name at the cursor.
+    /// e.g. eval "$(sb init bash)"
+    #[command(verbatim_doc_comment)]
+    Init {
+        shell: InitShell,
+    },
+
+    /// Suggest binaries for a mistyped command and offer
+    /// to run the closest one when it is unambiguous.
+    /// Call it from the command-not-found handler,
+    /// e.g. command_not_found_handle() { sb not-found "$@"; }
+    #[command(verbatim_doc_comment)]
+    NotFound {
+        command: String,
+
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Print the path of the config file,
+    /// $XDG_CONFIG_HOME/sb/config.toml.
+    /// Command-line arguments override its values.
+    #[command(verbatim_doc_comment)]
+    Config {
+        /// Print a commented template of the config file.
+        #[arg(long)]
+        print_default: bool,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
 #[derive(Default, Args, Debug)]
 #[group(multiple = false)]
 pub struct AppModeArg {
@@ -84,7 +328,6 @@ pub struct AppModeArg {
     #[arg(
         short = 's',
         long,
-        group = "simple_ui_mode",
         requires = "search_input",
         verbatim_doc_comment,
     )]
@@ -99,18 +342,74 @@ pub struct AppModeArg {
         verbatim_doc_comment,
     )]
     pub interactive: bool,
+
+    /// Run as a daemon that keeps the index warm and
+    /// answers searches over a Unix socket in
+    /// $XDG_RUNTIME_DIR/sb/daemon.sock with JSON lines like
+    /// {"query": "...", "limit": 10, "descriptions": true}.
+    /// Simple app mode uses it when it is running
+    /// with the same search settings.
+    #[arg(
+        long,
+        verbatim_doc_comment,
+    )]
+    pub daemon: bool,
 }
 
+#[derive(PartialEq)]
 pub enum AppMode {
     Simple,
     Interactive,
+    Daemon,
 }
 
 impl From<&AppModeArg> for AppMode {
     fn from(value: &AppModeArg) -> Self {
-        match (value.simple, value.interactive) {
+        match (value.simple, value.daemon) {
             (true, _) => AppMode::Simple,
+            (_, true) => AppMode::Daemon,
             _ => AppMode::Interactive,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn subcommand_names_are_inputs_after_args() {
+        let args = Cli::try_parse_from(["sb", "-s", "init"]).unwrap();
+
+        assert!(args.command.is_none());
+        assert!(args.app_mode.simple);
+        assert_eq!(args.input.as_deref(), Some("init"));
+    }
+
+    #[test]
+    fn subcommand_names_are_inputs_after_separator() {
+        let args = Cli::try_parse_from(["sb", "--", "config"]).unwrap();
+
+        assert!(args.command.is_none());
+        assert_eq!(args.input.as_deref(), Some("config"));
+    }
+
+    #[test]
+    fn frecency_weight_rejects_out_of_range_values() {
+        assert!(Cli::try_parse_from(["sb", "-f", "NaN", "ls"]).is_err());
+        assert!(Cli::try_parse_from(["sb", "-f", "inf", "ls"]).is_err());
+        assert!(Cli::try_parse_from(["sb", "-f", "1.5", "ls"]).is_err());
+        assert_eq!(Cli::try_parse_from(["sb", "-f", "0.5", "ls"]).unwrap().frecency_weight, 0.5);
+        assert_eq!(Cli::try_parse_from(["sb", "ls"]).unwrap().frecency_weight, 0.0);
+    }
+
+    #[test]
+    fn subcommands_are_parsed_first() {
+        let args = Cli::try_parse_from(["sb", "init", "bash"]).unwrap();
+
+        assert!(matches!(args.command, Some(Command::Init { shell: InitShell::Bash })));
+        assert!(args.input.is_none());
+    }
+}
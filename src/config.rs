@@ -0,0 +1,282 @@
+use std::{fs, path::PathBuf};
+
+use clap::{ArgMatches, CommandFactory, FromArgMatches, ValueEnum, parser::ValueSource};
+use color_eyre::eyre::{Result as RepResult, eyre};
+use toml::{Table, Value};
+
+use crate::{
+    cli::{Cli, Command, Height, check_weight, parse_height},
+    descriptions::{DescriptionProvider, init_description_providers},
+    keymap::{Action, InputMode, KeyBinding, Keymap, KeymapPreset, init_keymap},
+    query::Scoring,
+    theme::{Theme, ThemeName, init_theme},
+    xdg::sb_config_file,
+};
+
+const CONFIG_NAME: &str = "config.toml";
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+type ConfigResult<T> = Result<T, String>;
+
+pub fn get_config_path() -> Option<PathBuf> {
+    sb_config_file(CONFIG_NAME)
+}
+
+pub fn print_config(print_default: bool) {
+    if print_default {
+        print!("{}", DEFAULT_CONFIG);
+        return;
+    }
+
+    if let Some(path) = get_config_path() {
+        println!("{}", path.display());
+    }
+}
+
+fn get_bool(value: &Value) -> ConfigResult<bool> {
+    value
+        .as_bool()
+        .ok_or("expected a boolean".to_owned())
+}
+
+fn get_float(value: &Value) -> ConfigResult<f64> {
+    match value {
+        Value::Float(v) => Ok(*v),
+        Value::Integer(v) => Ok(*v as f64),
+        _ => Err("expected a number".to_owned()),
+    }
+}
+
+fn get_length(value: &Value) -> ConfigResult<usize> {
+    value
+        .as_integer()
+        .and_then(|v| usize::try_from(v).ok())
+        .ok_or("expected a positive integer".to_owned())
+}
+
+fn get_str(value: &Value) -> ConfigResult<&str> {
+    value
+        .as_str()
+        .ok_or("expected a string".to_owned())
+}
+
+fn get_str_list(value: &Value) -> ConfigResult<Vec<&str>> {
+    match value {
+        Value::String(v) => Ok(vec![v.as_str()]),
+        Value::Array(values) => values.iter().map(get_str).collect(),
+        _ => Err("expected a string or an array of strings".to_owned()),
+    }
+}
+
+fn get_height(value: &Value) -> ConfigResult<Height> {
+    match value {
+        Value::Integer(v) => parse_height(&v.to_string()),
+        Value::String(v) => parse_height(v),
+        _ => Err("expected a line count or a percentage".to_owned()),
+    }
+}
+
+fn get_shell_entries(value: &Value) -> ConfigResult<Option<String>> {
+    match value {
+        Value::Boolean(true) => Ok(Some(String::new())),
+        Value::Boolean(false) => Ok(None),
+        Value::String(v) => Ok(Some(v.clone())),
+        _ => Err("expected a boolean or a file".to_owned()),
+    }
+}
+
+fn get_scoring(value: &Value) -> ConfigResult<Scoring> {
+    let name = get_str(value)?;
+    Scoring::from_str(name, true).map_err(|_| format!("unknown matcher: {}", name))
+}
+
+fn get_table(value: &Value) -> ConfigResult<&Table> {
+    value
+        .as_table()
+        .ok_or("expected a table".to_owned())
+}
+
+fn set_default<T>(
+    matches: &ArgMatches,
+    id: &str,
+    field: &mut T,
+    value: ConfigResult<T>,
+) -> ConfigResult<()> {
+    let value = value?;
+
+    if matches.value_source(id) != Some(ValueSource::CommandLine) {
+        *field = value;
+    }
+
+    Ok(())
+}
+
+fn apply_defaults(args: &mut Cli, matches: &ArgMatches, table: &Table) -> ConfigResult<()> {
+    for (key, value) in table {
+        let result = match key.as_str() {
+            "descriptions" => set_default(matches, "show_descriptions", &mut args.show_descriptions, get_bool(value)),
+            "length" => set_default(matches, "result_length", &mut args.result_length, get_length(value).map(Some)),
+            "min_score" => set_default(matches, "min_score", &mut args.min_score, get_float(value)),
+            "frecency" => set_default(matches, "frecency_weight", &mut args.frecency_weight, get_float(value).and_then(check_weight)),
+            "incremental" => set_default(matches, "incremental", &mut args.incremental, get_bool(value)),
+            "expand_aliases" => set_default(matches, "expand_aliases", &mut args.expand_aliases, get_bool(value)),
+            "apps" => set_default(matches, "desktop_apps", &mut args.desktop_apps, get_bool(value)),
+            "toolchains" => set_default(matches, "toolchains", &mut args.toolchains, get_bool(value)),
+            "shell_entries" => set_default(matches, "shell_entries", &mut args.shell_entries, get_shell_entries(value)),
+            "full_width" => set_default(matches, "full_width", &mut args.full_width, get_bool(value)),
+            "height" => set_default(matches, "height", &mut args.height, get_height(value).map(Some)),
+            _ => Err("unknown key".to_owned()),
+        };
+
+        result.map_err(|e| format!("defaults.{}: {}", key, e))?;
+    }
+
+    Ok(())
+}
+
+fn apply_search(args: &mut Cli, matches: &ArgMatches, table: &Table) -> ConfigResult<()> {
+    for (key, value) in table {
+        let result = match key.as_str() {
+            "paths" => {
+                let paths = get_str_list(value).map(|paths| Some(paths.join(":")));
+                set_default(matches, "search_path", &mut args.search_path, paths)
+            },
+            "matcher" => set_default(matches, "matcher", &mut args.matcher, get_scoring(value).map(Some)),
+            _ => Err("unknown key".to_owned()),
+        };
+
+        result.map_err(|e| format!("search.{}: {}", key, e))?;
+    }
+
+    Ok(())
+}
+
+fn apply_descriptions(table: &Table) -> ConfigResult<()> {
+    for (key, value) in table {
+        let result = match key.as_str() {
+            "providers" => get_str_list(value).and_then(|names| {
+                let providers = names
+                    .into_iter()
+                    .map(|name| DescriptionProvider::from_name(name)
+                        .ok_or(format!("unknown provider: {}", name)))
+                    .collect::<ConfigResult<Vec<DescriptionProvider>>>()?;
+
+                init_description_providers(providers);
+                Ok(())
+            }),
+            _ => Err("unknown key".to_owned()),
+        };
+
+        result.map_err(|e| format!("descriptions.{}: {}", key, e))?;
+    }
+
+    Ok(())
+}
+
+fn apply_colors(table: &Table) -> ConfigResult<()> {
+    let mut theme = match table.get("theme") {
+        Some(value) => {
+            let name = get_str(value).map_err(|e| format!("colors.theme: {}", e))?;
+            let name = ThemeName::from_name(name).ok_or(format!("colors.theme: unknown theme: {}", name))?;
+            Theme::from_name(name)
+        },
+        None => Theme::default(),
+    };
+
+    for (key, value) in table.iter().filter(|(key, _)| *key != "theme") {
+        get_str(value)
+            .and_then(|color| theme.set_color(key, color))
+            .map_err(|e| format!("colors.{}: {}", key, e))?;
+    }
+
+    init_theme(theme);
+    Ok(())
+}
+
+fn bind_keys(keymap: &mut Keymap, mode: InputMode, prefix: &str, table: &Table) -> ConfigResult<()> {
+    for (key, value) in table {
+        let action = Action::from_name(key)
+            .ok_or(format!("{}.{}: unknown action", prefix, key))?;
+
+        let keys = get_str_list(value)
+            .map_err(|e| format!("{}.{}: {}", prefix, key, e))?;
+
+        for name in keys {
+            let binding = KeyBinding::parse(name)
+                .ok_or(format!("{}.{}: invalid key: {}", prefix, key, name))?;
+
+            keymap.bind(mode, binding, action);
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_keys(table: &Table) -> ConfigResult<()> {
+    let preset = match table.get("preset") {
+        Some(value) => {
+            let name = get_str(value).map_err(|e| format!("keys.preset: {}", e))?;
+            KeymapPreset::from_name(name).ok_or(format!("keys.preset: unknown preset: {}", name))?
+        },
+        None => KeymapPreset::Default,
+    };
+
+    let mut keymap = Keymap::from_preset(preset);
+    let mut bindings = table.clone();
+
+    bindings.remove("preset");
+
+    if let Some(value) = bindings.remove("normal") {
+        if !keymap.has_normal_mode() {
+            return Err("keys.normal: needs the vim preset".to_owned());
+        }
+
+        let normal = get_table(&value).map_err(|e| format!("keys.normal: {}", e))?;
+        bind_keys(&mut keymap, InputMode::Normal, "keys.normal", normal)?;
+    }
+
+    bind_keys(&mut keymap, InputMode::Insert, "keys", &bindings)?;
+
+    init_keymap(keymap);
+    Ok(())
+}
+
+fn apply_config(args: &mut Cli, matches: &ArgMatches, config: &Table) -> ConfigResult<()> {
+    for (section, value) in config {
+        let table = get_table(value).map_err(|e| format!("{}: {}", section, e))?;
+
+        match section.as_str() {
+            "defaults" => apply_defaults(args, matches, table)?,
+            "search" => apply_search(args, matches, table)?,
+            "descriptions" => apply_descriptions(table)?,
+            "colors" => apply_colors(table)?,
+            "keys" => apply_keys(table)?,
+            _ => return Err(format!("{}: unknown section", section)),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn load_args() -> RepResult<Cli> {
+    let matches = Cli::command().get_matches();
+    let mut args = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if let Some(Command::Config { .. }) = args.command {
+        return Ok(args);
+    }
+
+    let path = match get_config_path() {
+        Some(v) if v.is_file() => v,
+        _ => return Ok(args),
+    };
+
+    let config = fs::read_to_string(&path)?
+        .parse::<Table>()
+        .map_err(|e| eyre!("{}: {}", path.display(), e))?;
+
+    apply_config(&mut args, &matches, &config)
+        .map_err(|e| eyre!("{}: {}", path.display(), e))?;
+
+    Ok(args)
+}
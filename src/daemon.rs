@@ -0,0 +1,217 @@
+use std::{
+    env,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use serde_json::{Value, json};
+
+use crate::{
+    binaries::{
+        BinaryNode,
+        attach_file_infos,
+        attach_manpaths,
+        search_binaries,
+    },
+    cli::Cli,
+    json::binary_to_json,
+    query::Query,
+    xdg::sb_runtime_file,
+};
+
+const SOCKET_NAME: &str = "daemon.sock";
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Everything that changes which binaries are found or how they rank,
+// a daemon only answers clients whose settings equal its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchSettings {
+    pub search_path: Option<String>,
+    pub root_dir: Option<String>,
+    pub min_score: f64,
+    pub frecency_weight: f64,
+    pub matcher: Option<String>,
+    pub shell_entries: Option<String>,
+    pub expand_aliases: bool,
+    pub desktop_apps: bool,
+    pub toolchains: bool,
+}
+
+impl SearchSettings {
+    pub fn from_args(args: &Cli) -> Self {
+        Self {
+            search_path: args.search_path
+                .clone()
+                .or(env::var("PATH").ok()),
+            root_dir: args.root_dir
+                .as_ref()
+                .map(|dir| dir.to_string_lossy().to_string()),
+            min_score: args.min_score,
+            frecency_weight: args.frecency_weight,
+            matcher: args.matcher
+                .and_then(|matcher| matcher.to_possible_value())
+                .map(|value| value.get_name().to_owned()),
+            shell_entries: args.shell_entries.clone(),
+            expand_aliases: args.expand_aliases,
+            desktop_apps: args.desktop_apps,
+            toolchains: args.toolchains,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "search_path": self.search_path,
+            "root_dir": self.root_dir,
+            "min_score": self.min_score,
+            "frecency_weight": self.frecency_weight,
+            "matcher": self.matcher,
+            "shell_entries": self.shell_entries,
+            "expand_aliases": self.expand_aliases,
+            "desktop_apps": self.desktop_apps,
+            "toolchains": self.toolchains,
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let get_str = |key: &str| value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_owned());
+
+        let get_flag = |key: &str| value
+            .get(key)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        Some(Self {
+            search_path: get_str("search_path"),
+            root_dir: get_str("root_dir"),
+            min_score: value.get("min_score")?.as_f64()?,
+            frecency_weight: value.get("frecency_weight")?.as_f64()?,
+            matcher: get_str("matcher"),
+            shell_entries: get_str("shell_entries"),
+            expand_aliases: get_flag("expand_aliases"),
+            desktop_apps: get_flag("desktop_apps"),
+            toolchains: get_flag("toolchains"),
+        })
+    }
+}
+
+pub struct SearchRequest {
+    pub query: String,
+    pub limit: Option<usize>,
+    pub descriptions: bool,
+    pub details: bool,
+    pub settings: Option<SearchSettings>,
+}
+
+impl SearchRequest {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "query": self.query,
+            "limit": self.limit,
+            "descriptions": self.descriptions,
+            "details": self.details,
+            "settings": self.settings.as_ref().map(SearchSettings::to_json),
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let get_flag = |key: &str| value
+            .get(key)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        Some(Self {
+            query: value.get("query")?.as_str()?.to_owned(),
+            limit: value
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize),
+            descriptions: get_flag("descriptions"),
+            details: get_flag("details"),
+            settings: value
+                .get("settings")
+                .and_then(SearchSettings::from_json),
+        })
+    }
+}
+
+pub fn get_socket_path() -> Option<PathBuf> {
+    sb_runtime_file(SOCKET_NAME)
+}
+
+pub fn run_search_request(request: &SearchRequest) -> Value {
+    let mut search_result = search_binaries(&Query::parse(&request.query));
+    let total = search_result.total;
+
+    if let Some(limit) = request.limit {
+        search_result.truncate(limit);
+    }
+
+    let binaries = search_result
+        .owned_ordered_iter()
+        .collect::<Vec<BinaryNode>>();
+
+    if request.details {
+        attach_file_infos(&binaries);
+    }
+
+    if request.descriptions {
+        attach_manpaths(&binaries);
+    }
+
+    let results = binaries
+        .iter()
+        .map(|binary| {
+            let readable_binary = binary.read().unwrap();
+
+            let description = match request.descriptions {
+                true => readable_binary.get_description(),
+                false => None,
+            };
+
+            binary_to_json(&readable_binary, description.as_deref())
+        })
+        .collect::<Vec<Value>>();
+
+    json!({
+        "total": total,
+        "results": results,
+    })
+}
+
+fn send_request(mut stream: UnixStream, request: &SearchRequest) -> Result<Value, String> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT)).map_err(|e| e.to_string())?;
+
+    let request = format!("{}\n", request.to_json());
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).map_err(|e| e.to_string())?;
+
+    let response = serde_json::from_str::<Value>(&line).map_err(|e| e.to_string())?;
+
+    match (response.get("results"), response.get("error")) {
+        (Some(Value::Array(_)), _) => Ok(response),
+        (_, Some(Value::String(error))) => Err(error.clone()),
+        _ => Err("Unexpected response".to_owned()),
+    }
+}
+
+// No daemon running is the usual case, only a failing one is worth a warning.
+pub fn query_daemon(request: &SearchRequest) -> Option<Value> {
+    let stream = UnixStream::connect(get_socket_path()?).ok()?;
+
+    match send_request(stream, request) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            eprintln!("sb: daemon: {}, searching without it", e);
+            None
+        },
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    sync::{Arc, mpsc},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::eyre::{Result as RepResult, eyre};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::{Value, json};
+
+use crate::{
+    app::App,
+    binaries::{get_watch_dirs, load_binaries, reload_binaries},
+    cli::Cli,
+    daemon::{SearchRequest, SearchSettings, get_socket_path, run_search_request},
+    frecency::{get_picks_path, reload_usages},
+};
+
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+pub struct DaemonApp {
+    args: Cli,
+}
+
+impl App for DaemonApp {
+    fn with_args(args: Cli) -> Self {
+        Self { args }
+    }
+
+    fn run(&mut self) -> RepResult<()> {
+        let socket_path = get_socket_path()
+            .ok_or(eyre!("No runtime or state directory for the socket"))?;
+
+        if UnixStream::connect(&socket_path).is_ok() {
+            return Err(eyre!("Daemon is already running at {}", socket_path.display()));
+        }
+
+        let _ = fs::remove_file(&socket_path);
+
+        if let Some(dir) = socket_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        load_binaries();
+
+        let _watcher = watch_search_paths()?;
+        let _picks_watcher = watch_picks();
+        let listener = UnixListener::bind(&socket_path)?;
+        let descriptions = self.args.show_descriptions;
+        let settings = Arc::new(SearchSettings::from_args(&self.args));
+
+        eprintln!("Listening on {}", socket_path.display());
+
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let settings = settings.clone();
+            thread::spawn(move || handle_client(stream, descriptions, &settings));
+        }
+
+        Ok(())
+    }
+}
+
+fn handle_request(line: &str, descriptions: bool, settings: &SearchSettings) -> Value {
+    let request = serde_json::from_str::<Value>(line)
+        .ok()
+        .and_then(|value| SearchRequest::from_json(&value));
+
+    match request {
+        Some(request) if request.settings.as_ref().is_some_and(|v| v != settings) => {
+            json!({ "error": "Search settings differ from the daemon" })
+        },
+        Some(mut request) => {
+            request.descriptions &= descriptions;
+            run_search_request(&request)
+        },
+        None => json!({ "error": "Invalid request" }),
+    }
+}
+
+fn handle_client(stream: UnixStream, descriptions: bool, settings: &SearchSettings) {
+    let reader = match stream.try_clone() {
+        Ok(v) => BufReader::new(v),
+        Err(_) => return,
+    };
+
+    let mut writer = stream;
+
+    for line in reader.lines().map_while(Result::ok) {
+        let response = format!("{}\n", handle_request(&line, descriptions, settings));
+
+        if writer.write_all(response.as_bytes()).is_err() {
+            break;
+        }
+    }
+}
+
+fn is_index_event(event: &notify::Result<Event>) -> bool {
+    match event {
+        Ok(event) => !event.kind.is_access(),
+        Err(_) => false,
+    }
+}
+
+fn watch_search_paths() -> notify::Result<RecommendedWatcher> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+
+    for dir in get_watch_dirs() {
+        let _ = watcher.watch(&dir, RecursiveMode::NonRecursive);
+    }
+
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            if !is_index_event(&event) {
+                continue;
+            }
+
+            thread::sleep(RELOAD_DEBOUNCE);
+            while receiver.try_recv().is_ok() {}
+
+            reload_binaries();
+        }
+    });
+
+    Ok(watcher)
+}
+
+fn watch_picks() -> Option<RecommendedWatcher> {
+    let picks_path = get_picks_path()?;
+    let dir = picks_path.parent()?;
+
+    fs::create_dir_all(dir).ok()?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).ok()?;
+
+    watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            let is_picks_event = match &event {
+                Ok(event) => !event.kind.is_access() && event.paths.contains(&picks_path),
+                Err(_) => false,
+            };
+
+            if !is_picks_event {
+                continue;
+            }
+
+            thread::sleep(RELOAD_DEBOUNCE);
+            while receiver.try_recv().is_ok() {}
+
+            reload_usages();
+        }
+    });
+
+    Some(watcher)
+}
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, LazyLock, Mutex},
+    sync::{Arc, LazyLock, Mutex, OnceLock},
 };
 
 use crate::{
@@ -11,6 +11,40 @@ pub struct Description {
     pub value: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DescriptionProvider {
+    Shell,
+    Man,
+    Desktop,
+}
+
+impl DescriptionProvider {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "shell" => Some(Self::Shell),
+            "man" => Some(Self::Man),
+            "desktop" => Some(Self::Desktop),
+            _ => None,
+        }
+    }
+}
+
+const DEFAULT_DESCRIPTION_PROVIDERS: [DescriptionProvider; 3] = [
+    DescriptionProvider::Shell,
+    DescriptionProvider::Man,
+    DescriptionProvider::Desktop,
+];
+
+static DESCRIPTION_PROVIDERS: OnceLock<Vec<DescriptionProvider>> = OnceLock::new();
+
+pub fn init_description_providers(providers: Vec<DescriptionProvider>) {
+    DESCRIPTION_PROVIDERS.get_or_init(|| providers);
+}
+
+pub fn get_description_providers() -> &'static [DescriptionProvider] {
+    DESCRIPTION_PROVIDERS.get_or_init(|| DEFAULT_DESCRIPTION_PROVIDERS.to_vec())
+}
+
 type Descriptions = HashMap<String, Arc<Description>>;
 
 static DESCRIPTIONS: LazyLock<Mutex<Descriptions>> =
@@ -0,0 +1,224 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{
+    root::{get_root_dir, rooted},
+    xdg::{data_dirs, system_data_dirs},
+};
+
+const APPLICATIONS_DIR: &str = "applications";
+const DESKTOP_EXTENSION: &str = "desktop";
+const DESKTOP_ENTRY_GROUP: &str = "[Desktop Entry]";
+
+const FLATPAK_COMMAND: &str = "flatpak";
+const FLATPAK_EXPORTS: &str = "/var/lib/flatpak/exports/bin";
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub comment: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+impl DesktopEntry {
+    pub fn get_search_names(&self) -> impl Iterator<Item = String> {
+        let names = [Some(&self.name), self.generic_name.as_ref()];
+
+        let words = names
+            .into_iter()
+            .flatten()
+            .flat_map(|name| {
+                let words = name.split_whitespace();
+                let whole = (words.clone().count() > 1).then_some(name.as_str());
+
+                whole.into_iter().chain(words)
+            })
+            .chain(self.keywords.iter().map(|k| k.as_str()));
+
+        words.map(|word| word.to_lowercase())
+    }
+}
+
+pub struct DesktopApp {
+    pub exec_name: String,
+    pub exec_path: Option<PathBuf>,
+    pub entry: DesktopEntry,
+}
+
+static DESKTOP_ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub fn init_desktop_apps(enabled: bool) {
+    DESKTOP_ENABLED.get_or_init(|| enabled);
+}
+
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut quoted = false;
+
+    for c in exec.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !arg.is_empty() {
+                    args.push(std::mem::take(&mut arg));
+                }
+            },
+            c => arg.push(c),
+        }
+    }
+
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+
+    args
+}
+
+fn resolve_exec(exec: &str, file_stem: &str) -> Option<(String, Option<PathBuf>)> {
+    let args = split_exec(exec);
+
+    let mut args = args
+        .iter()
+        .skip_while(|arg| *arg == "env" || arg.contains('='));
+
+    let command = args.next()?;
+    let path = Path::new(command);
+    let name = path.file_name()?.to_string_lossy().to_string();
+
+    if name == FLATPAK_COMMAND && args.any(|arg| arg == "run") {
+        let exported = rooted(Path::new(FLATPAK_EXPORTS)).join(file_stem);
+        return Some((file_stem.to_owned(), Some(exported)));
+    }
+
+    let exec_path = path
+        .is_absolute()
+        .then(|| rooted(path));
+
+    Some((name, exec_path))
+}
+
+fn parse_desktop_file(path: &Path) -> Option<DesktopApp> {
+    let content = fs::read_to_string(path).ok()?;
+    let file_stem = path.file_stem()?.to_string_lossy();
+
+    let mut in_entry_group = false;
+
+    let mut name = None;
+    let mut generic_name = None;
+    let mut comment = None;
+    let mut keywords = Vec::new();
+    let mut exec = None;
+    let mut is_application = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_entry_group = line == DESKTOP_ENTRY_GROUP;
+            continue;
+        }
+
+        if !in_entry_group {
+            continue;
+        }
+
+        let (key, value) = match line.split_once('=') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => continue,
+        };
+
+        match key {
+            "Type" => is_application = value == "Application",
+            "Hidden" if value == "true" => return None,
+            "Name" => name = Some(value.to_owned()),
+            "GenericName" => generic_name = Some(value.to_owned()),
+            "Comment" => comment = Some(value.to_owned()),
+            "Exec" => exec = Some(value.to_owned()),
+            "Keywords" => {
+                keywords = value
+                    .split(';')
+                    .filter(|k| !k.is_empty())
+                    .map(|k| k.to_owned())
+                    .collect();
+            },
+            _ => {},
+        }
+    }
+
+    if !is_application {
+        return None;
+    }
+
+    let (exec_name, exec_path) = resolve_exec(&exec?, &file_stem)?;
+
+    let entry = DesktopEntry {
+        name: name?,
+        generic_name,
+        comment,
+        keywords,
+    };
+
+    Some(DesktopApp { exec_name, exec_path, entry })
+}
+
+fn get_data_dirs() -> Vec<PathBuf> {
+    // The host's XDG dirs and home don't belong to the root.
+    match get_root_dir() {
+        Some(_) => system_data_dirs().iter().map(|dir| rooted(dir)).collect(),
+        None => data_dirs(),
+    }
+}
+
+// Files in subdirectories get IDs like "kde-org.kde.konsole.desktop".
+fn collect_desktop_files(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let id = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_desktop_files(&path, &format!("{}-", id), files);
+            continue;
+        }
+
+        if path.extension().is_some_and(|ext| ext == DESKTOP_EXTENSION) {
+            files.push((id, path));
+        }
+    }
+}
+
+pub fn get_desktop_apps() -> Vec<DesktopApp> {
+    if !DESKTOP_ENABLED.get().copied().unwrap_or(false) {
+        return vec![];
+    }
+
+    let mut seen = HashSet::new();
+    let mut apps = Vec::new();
+    let mut files = Vec::new();
+
+    for dir in get_data_dirs() {
+        collect_desktop_files(&dir.join(APPLICATIONS_DIR), "", &mut files);
+    }
+
+    for (id, path) in files {
+        if !seen.insert(id) {
+            continue;
+        }
+
+        if let Some(app) = parse_desktop_file(&path) {
+            apps.push(app);
+        }
+    }
+
+    apps
+}
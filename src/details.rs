@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    fs,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    binaries::{Binary, get_shadowed_paths},
+    filetype::detect_interpreter,
+    man::get_man_sections,
+    root::{metadata, rooted},
+};
+
+const PASSWD_PATH: &str = "/etc/passwd";
+const GROUP_PATH: &str = "/etc/group";
+const SIZE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+const PERMISSION_CHARS: [char; 3] = ['r', 'w', 'x'];
+
+static USER_NAMES: LazyLock<HashMap<u32, String>> =
+    LazyLock::new(|| read_id_names(PASSWD_PATH));
+static GROUP_NAMES: LazyLock<HashMap<u32, String>> =
+    LazyLock::new(|| read_id_names(GROUP_PATH));
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct BinaryDetails {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub interpreter: Option<String>,
+    pub shadowed: Vec<PathBuf>,
+    pub sections: Vec<String>,
+}
+
+impl BinaryDetails {
+    pub fn get_size(&self) -> String {
+        let mut value = self.size as f64;
+        let mut unit = 0;
+
+        // Values that would print as 1024.0 move up a unit too.
+        while value >= 1023.95 && unit < SIZE_UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        match unit {
+            0 => format!("{} B", self.size),
+            _ => format!("{:.1} {} ({} bytes)", value, SIZE_UNITS[unit], self.size),
+        }
+    }
+
+    pub fn get_modified(&self) -> Option<String> {
+        let secs = self.modified?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs() as i64;
+
+        let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+        let time = secs.rem_euclid(86400);
+
+        Some(format!(
+            "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+            year, month, day, time / 3600, time % 3600 / 60,
+        ))
+    }
+
+    pub fn get_permissions(&self) -> String {
+        let mut permissions = String::from("-");
+
+        for shift in [6, 3, 0] {
+            let bits = self.mode >> shift & 0o7;
+
+            for (i, c) in PERMISSION_CHARS.iter().enumerate() {
+                match bits & (0o4 >> i) != 0 {
+                    true => permissions.push(*c),
+                    false => permissions.push('-'),
+                }
+            }
+        }
+
+        let special = [(0o4000, 3, 's'), (0o2000, 6, 's'), (0o1000, 9, 't')];
+
+        for (bit, index, c) in special {
+            if self.mode & bit == 0 {
+                continue;
+            }
+
+            let c = match permissions.as_bytes()[index] {
+                b'x' => c,
+                _ => c.to_ascii_uppercase(),
+            };
+
+            permissions.replace_range(index..index + 1, &c.to_string());
+        }
+
+        format!("{} ({:o})", permissions, self.mode & 0o7777)
+    }
+
+    pub fn get_owner(&self) -> String {
+        let user = USER_NAMES
+            .get(&self.uid)
+            .cloned()
+            .unwrap_or(self.uid.to_string());
+
+        let group = GROUP_NAMES
+            .get(&self.gid)
+            .cloned()
+            .unwrap_or(self.gid.to_string());
+
+        format!("{}:{}", user, group)
+    }
+}
+
+fn read_id_names(path: &str) -> HashMap<u32, String> {
+    fs::read_to_string(rooted(Path::new(path)))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+
+            let name = fields.next()?;
+            let id = fields.nth(1)?.parse::<u32>().ok()?;
+
+            Some((id, name.to_owned()))
+        })
+        .collect()
+}
+
+// Days since the epoch to a proleptic Gregorian date,
+// from Howard Hinnant's "civil_from_days".
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+pub fn detect_details(binary: &Binary) -> Option<BinaryDetails> {
+    let metadata = metadata(&binary.path).ok()?;
+
+    Some(BinaryDetails {
+        size: metadata.size(),
+        modified: metadata.modified().ok(),
+        mode: metadata.mode(),
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        interpreter: detect_interpreter(&binary.path),
+        shadowed: get_shadowed_paths(binary),
+        sections: get_man_sections(&binary.name)
+            .cloned()
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(size: u64, mode: u32) -> BinaryDetails {
+        BinaryDetails {
+            size,
+            modified: None,
+            mode,
+            uid: 0,
+            gid: 0,
+            interpreter: None,
+            shadowed: vec![],
+            sections: vec![],
+        }
+    }
+
+    #[test]
+    fn permissions_show_special_bits() {
+        assert_eq!(details(0, 0o100755).get_permissions(), "-rwxr-xr-x (755)");
+        assert_eq!(details(0, 0o104755).get_permissions(), "-rwsr-xr-x (4755)");
+        assert_eq!(details(0, 0o104644).get_permissions(), "-rwSr--r-- (4644)");
+        assert_eq!(details(0, 0o102755).get_permissions(), "-rwxr-sr-x (2755)");
+        assert_eq!(details(0, 0o102644).get_permissions(), "-rw-r-Sr-- (2644)");
+        assert_eq!(details(0, 0o101777).get_permissions(), "-rwxrwxrwt (1777)");
+        assert_eq!(details(0, 0o101666).get_permissions(), "-rw-rw-rwT (1666)");
+    }
+
+    #[test]
+    fn civil_dates_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19723), (2024, 1, 1));
+    }
+
+    #[test]
+    fn modified_time_is_utc() {
+        let mut details = details(0, 0);
+        details.modified = Some(UNIX_EPOCH + std::time::Duration::from_secs(86399));
+
+        assert_eq!(details.get_modified().as_deref(), Some("1970-01-01 23:59 UTC"));
+    }
+
+    #[test]
+    fn sizes_round_to_units() {
+        assert_eq!(details(0, 0).get_size(), "0 B");
+        assert_eq!(details(1023, 0).get_size(), "1023 B");
+        assert_eq!(details(1024, 0).get_size(), "1.0 KiB (1024 bytes)");
+        assert_eq!(details(1536, 0).get_size(), "1.5 KiB (1536 bytes)");
+        assert_eq!(details(1048575, 0).get_size(), "1.0 MiB (1048575 bytes)");
+        assert_eq!(details(5 << 30, 0).get_size(), "5.0 GiB (5368709120 bytes)");
+        assert_eq!(details(2048 << 40, 0).get_size(), "2048.0 TiB (2251799813685248 bytes)");
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    packages::{Package, get_package_owner},
+    root::canonicalize,
+};
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const APPIMAGE_MAGIC: &[u8] = b"AI";
+const SHEBANG: &[u8] = b"#!";
+
+const HEADER_SIZE: usize = 512;
+
+const ELF_CLASS_64: u8 = 2;
+const ELF_DATA_BE: u8 = 2;
+const PT_INTERP: u32 = 3;
+const PHENTSIZE_32: u16 = 32;
+const PHENTSIZE_64: u16 = 56;
+const MAX_PROGRAM_HEADERS: u16 = 256;
+
+const FLATPAK_RUN: &str = "flatpak run";
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Linking {
+    Static,
+    Dynamic,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum FileType {
+    Elf { arch: String, linking: Linking },
+    Script { interpreter: String },
+    AppImage { arch: String },
+    Flatpak { app_id: String },
+    Unknown,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct FileInfo {
+    pub file_type: FileType,
+    pub symlink_target: Option<PathBuf>,
+    pub package: Option<Arc<Package>>,
+}
+
+impl fmt::Display for Linking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Linking::Static => write!(f, "static"),
+            Linking::Dynamic => write!(f, "dynamic"),
+        }
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileType::Elf { arch, linking } => write!(f, "ELF {}, {}ally linked", arch, linking),
+            FileType::Script { interpreter } => write!(f, "{} script", interpreter),
+            FileType::AppImage { arch } => write!(f, "AppImage {}", arch),
+            FileType::Flatpak { app_id } => write!(f, "Flatpak wrapper for {}", app_id),
+            FileType::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FileType {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            FileType::Elf { .. } => "elf",
+            FileType::Script { .. } => "script",
+            FileType::AppImage { .. } => "appimage",
+            FileType::Flatpak { .. } => "flatpak",
+            FileType::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match (self, value) {
+            (FileType::Elf { .. } | FileType::AppImage { .. }, "elf" | "binary") => true,
+            (FileType::Elf { linking, .. }, "static" | "dynamic") => linking.to_string() == value,
+            (FileType::Elf { arch, .. } | FileType::AppImage { arch }, _) if arch == value => true,
+            (FileType::Script { .. }, "script") => true,
+            (FileType::Script { interpreter }, _) => interpreter.starts_with(value),
+            _ => self.get_name() == value,
+        }
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let bytes: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+
+    Some(match big_endian {
+        true => u16::from_be_bytes(bytes),
+        false => u16::from_le_bytes(bytes),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+
+    Some(match big_endian {
+        true => u32::from_be_bytes(bytes),
+        false => u32::from_le_bytes(bytes),
+    })
+}
+
+fn read_u64(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u64> {
+    let bytes: [u8; 8] = bytes.get(offset..offset + 8)?.try_into().ok()?;
+
+    Some(match big_endian {
+        true => u64::from_be_bytes(bytes),
+        false => u64::from_le_bytes(bytes),
+    })
+}
+
+fn get_elf_arch(machine: u16) -> String {
+    let arch = match machine {
+        0x03 => "i386",
+        0x08 => "mips",
+        0x14 => "powerpc",
+        0x15 => "powerpc64",
+        0x16 => "s390",
+        0x28 => "arm",
+        0x2b => "sparc64",
+        0x3e => "x86_64",
+        0xb7 => "aarch64",
+        0xf3 => "riscv",
+        0x102 => "loongarch",
+        _ => return format!("machine {:#x}", machine),
+    };
+
+    arch.to_owned()
+}
+
+fn read_program_headers(
+    file: &mut File,
+    header: &[u8],
+    is_64: bool,
+    big_endian: bool,
+) -> Option<(Vec<u8>, usize)> {
+    let (phoff, phentsize, phnum) = match is_64 {
+        true => (
+            read_u64(header, 32, big_endian)?,
+            read_u16(header, 54, big_endian)?,
+            read_u16(header, 56, big_endian)?,
+        ),
+        false => (
+            read_u32(header, 28, big_endian)? as u64,
+            read_u16(header, 42, big_endian)?,
+            read_u16(header, 44, big_endian)?,
+        ),
+    };
+
+    let expected_entsize = match is_64 {
+        true => PHENTSIZE_64,
+        false => PHENTSIZE_32,
+    };
+
+    if phentsize != expected_entsize || phnum > MAX_PROGRAM_HEADERS {
+        return None;
+    }
+
+    let size = phentsize as u64 * phnum as u64;
+    let file_size = file.metadata().ok()?.len();
+
+    if phoff.checked_add(size)? > file_size {
+        return None;
+    }
+
+    let mut program_headers = vec![0u8; size as usize];
+
+    file.seek(SeekFrom::Start(phoff)).ok()?;
+    file.read_exact(&mut program_headers).ok()?;
+
+    Some((program_headers, phentsize as usize))
+}
+
+fn get_elf_linking(file: &mut File, header: &[u8], is_64: bool, big_endian: bool) -> Option<Linking> {
+    let (program_headers, entry_size) = read_program_headers(file, header, is_64, big_endian)?;
+
+    let has_interpreter = program_headers
+        .chunks_exact(entry_size)
+        .any(|ph| read_u32(ph, 0, big_endian) == Some(PT_INTERP));
+
+    match has_interpreter {
+        true => Some(Linking::Dynamic),
+        false => Some(Linking::Static),
+    }
+}
+
+fn read_elf_interpreter(file: &mut File, header: &[u8]) -> Option<String> {
+    let is_64 = *header.get(4)? == ELF_CLASS_64;
+    let big_endian = *header.get(5)? == ELF_DATA_BE;
+
+    let (program_headers, entry_size) = read_program_headers(file, header, is_64, big_endian)?;
+
+    let interp = program_headers
+        .chunks_exact(entry_size)
+        .find(|ph| read_u32(ph, 0, big_endian) == Some(PT_INTERP))?;
+
+    let (offset, size) = match is_64 {
+        true => (
+            read_u64(interp, 8, big_endian)?,
+            read_u64(interp, 32, big_endian)?,
+        ),
+        false => (
+            read_u32(interp, 4, big_endian)? as u64,
+            read_u32(interp, 16, big_endian)? as u64,
+        ),
+    };
+
+    let mut path = vec![0u8; size.min(HEADER_SIZE as u64) as usize];
+
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    file.read_exact(&mut path).ok()?;
+
+    let path = String::from_utf8_lossy(&path)
+        .trim_end_matches('\0')
+        .to_owned();
+
+    Some(path)
+}
+
+fn detect_elf(file: &mut File, header: &[u8]) -> Option<FileType> {
+    let is_64 = *header.get(4)? == ELF_CLASS_64;
+    let big_endian = *header.get(5)? == ELF_DATA_BE;
+
+    let arch = get_elf_arch(read_u16(header, 18, big_endian)?);
+
+    if header.get(8..10) == Some(APPIMAGE_MAGIC) {
+        return Some(FileType::AppImage { arch });
+    }
+
+    let linking = get_elf_linking(file, header, is_64, big_endian)?;
+
+    Some(FileType::Elf { arch, linking })
+}
+
+fn get_flatpak_app_id(content: &str) -> Option<String> {
+    let line = content
+        .lines()
+        .find(|line| line.contains(FLATPAK_RUN))?;
+
+    line.split_whitespace()
+        .skip_while(|arg| *arg != "run")
+        .skip(1)
+        .find(|arg| !arg.starts_with('-'))
+        .map(|arg| arg.to_owned())
+}
+
+fn detect_script(header: &[u8]) -> Option<FileType> {
+    let content = String::from_utf8_lossy(header);
+    let shebang = content.lines().next()?;
+
+    let mut args = shebang[SHEBANG.len()..]
+        .split_whitespace()
+        .map(|arg| arg.rsplit('/').next().unwrap_or(arg));
+
+    let mut interpreter = args.next()?;
+
+    if interpreter == "env" {
+        interpreter = args.find(|arg| !arg.starts_with('-') && !arg.contains('='))?;
+    }
+
+    if let Some(app_id) = get_flatpak_app_id(&content) {
+        return Some(FileType::Flatpak { app_id });
+    }
+
+    Some(FileType::Script { interpreter: interpreter.to_owned() })
+}
+
+fn read_header(path: &Path) -> Option<(File, Vec<u8>)> {
+    let path = canonicalize(path).ok()?;
+    let mut file = File::open(path).ok()?;
+
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    (&mut file).take(HEADER_SIZE as u64).read_to_end(&mut header).ok()?;
+
+    Some((file, header))
+}
+
+pub fn detect_file_type(path: &Path) -> FileType {
+    let (mut file, header) = match read_header(path) {
+        Some(v) => v,
+        None => return FileType::Unknown,
+    };
+
+    let file_type = if header.starts_with(ELF_MAGIC) {
+        detect_elf(&mut file, &header)
+    } else if header.starts_with(SHEBANG) {
+        detect_script(&header)
+    } else {
+        None
+    };
+
+    file_type.unwrap_or(FileType::Unknown)
+}
+
+pub fn detect_interpreter(path: &Path) -> Option<String> {
+    let (mut file, header) = read_header(path)?;
+
+    if header.starts_with(ELF_MAGIC) {
+        return read_elf_interpreter(&mut file, &header);
+    }
+
+    if header.starts_with(SHEBANG) {
+        let content = String::from_utf8_lossy(&header);
+        let shebang = content.lines().next()?;
+
+        return Some(shebang[SHEBANG.len()..].trim().to_owned());
+    }
+
+    None
+}
+
+pub fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|md| md.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+pub fn detect_file_info(path: &Path) -> FileInfo {
+    let symlink_target = match is_symlink(path) {
+        true => canonicalize(path).ok(),
+        false => None,
+    };
+
+    FileInfo {
+        file_type: detect_file_type(path),
+        symlink_target,
+        package: get_package_owner(path),
+    }
+}
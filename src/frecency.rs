@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    path::{Path, PathBuf},
+    sync::{LazyLock, OnceLock, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::xdg::{data_home, home_dir, sb_state_file};
+
+const PICKS_FILE: &str = "picks";
+const PICK_FACTOR: f64 = 5.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+const COMMAND_PREFIXES: [&str; 6] = [
+    "sudo", "doas", "env", "exec", "time", "nohup",
+];
+
+struct Pick {
+    count: u64,
+    last: u64,
+}
+
+type Picks = HashMap<String, Pick>;
+
+struct Usages {
+    scores: HashMap<String, f64>,
+    max: f64,
+}
+
+static FRECENCY_WEIGHT: OnceLock<f64> = OnceLock::new();
+static USAGES: LazyLock<RwLock<Usages>> =
+    LazyLock::new(|| RwLock::new(init_usages()));
+
+pub fn init_frecency_weight(weight: f64) {
+    FRECENCY_WEIGHT.get_or_init(|| weight.clamp(0.0, 1.0));
+}
+
+fn get_frecency_weight() -> f64 {
+    FRECENCY_WEIGHT.get().copied().unwrap_or(0.0)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn recency_factor(last: u64, now: u64) -> f64 {
+    match now.saturating_sub(last) {
+        age if age < HOUR => 4.0,
+        age if age < DAY => 2.0,
+        age if age < WEEK => 1.0,
+        _ => 0.5,
+    }
+}
+
+pub fn get_picks_path() -> Option<PathBuf> {
+    sb_state_file(PICKS_FILE)
+}
+
+fn read_picks(path: &Path) -> Picks {
+    let content = match fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(_) => return Picks::new(),
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+
+            let name = fields.next()?;
+            let count = fields.next()?.parse().ok()?;
+            let last = fields.next()?.parse().ok()?;
+
+            Some((name.to_owned(), Pick { count, last }))
+        })
+        .collect()
+}
+
+fn write_picks(path: &Path, picks: &Picks) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = picks
+        .iter()
+        .map(|(name, pick)| format!("{}\t{}\t{}\n", name, pick.count, pick.last))
+        .collect::<String>();
+
+    fs::write(path, content)
+}
+
+pub fn record_pick(name: &str) {
+    let path = match get_picks_path() {
+        Some(v) => v,
+        None => return,
+    };
+
+    let mut picks = read_picks(&path);
+
+    let pick = picks
+        .entry(name.to_owned())
+        .or_insert(Pick { count: 0, last: 0 });
+
+    pick.count += 1;
+    pick.last = now();
+
+    let _ = write_picks(&path, &picks);
+}
+
+fn get_command_names(line: &str) -> impl Iterator<Item = &str> {
+    line.split(['|', ';', '&'])
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .find(|word| {
+                    !word.contains('=')
+                    && !COMMAND_PREFIXES.contains(word)
+                })
+        })
+        .map(|word| word.rsplit('/').next().unwrap_or(word))
+}
+
+fn get_history_lines(path: &Path) -> Vec<String> {
+    match fs::read(path) {
+        Ok(v) => String::from_utf8_lossy(&v)
+            .lines()
+            .map(|line| line.to_owned())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+fn get_bash_history() -> Vec<String> {
+    let path = env::var_os("HISTFILE")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".bash_history")));
+
+    let path = match path {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    get_history_lines(&path)
+        .into_iter()
+        .filter(|line| !line.starts_with('#'))
+        .collect()
+}
+
+fn get_zsh_history() -> Vec<String> {
+    let dir = env::var_os("ZDOTDIR")
+        .map(PathBuf::from)
+        .or_else(home_dir);
+
+    let path = match dir {
+        Some(v) => v.join(".zsh_history"),
+        None => return vec![],
+    };
+
+    get_history_lines(&path)
+        .into_iter()
+        .map(|line| match line.starts_with(": ") {
+            true => line
+                .split_once(';')
+                .map(|(_, command)| command.to_owned())
+                .unwrap_or_default(),
+            false => line,
+        })
+        .collect()
+}
+
+fn get_fish_history() -> Vec<String> {
+    let path = match data_home() {
+        Some(v) => v.join("fish").join("fish_history"),
+        None => return vec![],
+    };
+
+    get_history_lines(&path)
+        .into_iter()
+        .filter_map(|line| {
+            line.strip_prefix("- cmd: ")
+                .map(|command| command.to_owned())
+        })
+        .collect()
+}
+
+fn init_usages() -> Usages {
+    let mut scores = HashMap::<String, f64>::new();
+
+    let histories = [
+        get_bash_history(),
+        get_zsh_history(),
+        get_fish_history(),
+    ];
+
+    for line in histories.iter().flatten() {
+        for name in get_command_names(line) {
+            *scores.entry(name.to_owned()).or_default() += 1.0;
+        }
+    }
+
+    let picks = get_picks_path()
+        .map(|path| read_picks(&path))
+        .unwrap_or_default();
+
+    let now = now();
+
+    for (name, pick) in picks {
+        let score = pick.count as f64
+            * PICK_FACTOR
+            * recency_factor(pick.last, now);
+
+        *scores.entry(name).or_default() += score;
+    }
+
+    let max = scores
+        .values()
+        .fold(0.0, |acc: f64, v| acc.max(*v));
+
+    Usages { scores, max }
+}
+
+pub fn reload_usages() {
+    let usages = init_usages();
+    *USAGES.write().unwrap() = usages;
+}
+
+fn get_usage_boost(name: &str) -> f64 {
+    let usages = USAGES.read().unwrap();
+
+    if usages.max <= 0.0 {
+        return 0.0;
+    }
+
+    let score = match usages.scores.get(name) {
+        Some(v) => *v,
+        None => return 0.0,
+    };
+
+    score.ln_1p() / usages.max.ln_1p()
+}
+
+pub fn blend_frecency(name: &str, similarity: f64) -> f64 {
+    let weight = get_frecency_weight();
+
+    if weight <= 0.0 {
+        return similarity;
+    }
+
+    let boost = get_usage_boost(name);
+
+    similarity * ((1.0 - weight) + weight * boost)
+}
@@ -0,0 +1,174 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::xdg::sb_state_file;
+
+const HISTORY_FILE: &str = "history";
+const HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub query: String,
+    pub accepted: Vec<String>,
+}
+
+fn get_history_path() -> Option<PathBuf> {
+    sb_state_file(HISTORY_FILE)
+}
+
+fn sanitize(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+fn read_entries(path: &Path) -> Vec<HistoryEntry> {
+    let content = match fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+
+            let query = fields.next().filter(|query| !query.is_empty())?;
+            let accepted = fields.map(str::to_owned).collect();
+
+            Some(HistoryEntry { query: query.to_owned(), accepted })
+        })
+        .collect()
+}
+
+fn write_entries(path: &Path, entries: &[HistoryEntry]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = entries
+        .iter()
+        .map(|entry| {
+            let fields = [&entry.query]
+                .into_iter()
+                .chain(&entry.accepted)
+                .map(|field| sanitize(field))
+                .collect::<Vec<String>>();
+
+            fields.join("\t") + "\n"
+        })
+        .collect::<String>();
+
+    fs::write(path, content)
+}
+
+pub fn read_history() -> Vec<HistoryEntry> {
+    match get_history_path() {
+        Some(path) => read_entries(&path),
+        None => vec![],
+    }
+}
+
+pub fn get_last_query() -> Option<String> {
+    read_history()
+        .pop()
+        .map(|entry| entry.query)
+}
+
+// A repeated query moves to the end instead of being listed twice.
+fn push_entry(entries: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    entries.retain(|v| v.query != entry.query);
+    entries.push(entry);
+
+    let overflow = entries.len().saturating_sub(HISTORY_LIMIT);
+    entries.drain(..overflow);
+}
+
+pub fn record_history(query: &str, accepted: &[String]) {
+    let query = sanitize(query.trim());
+
+    if query.is_empty() {
+        return;
+    }
+
+    let path = match get_history_path() {
+        Some(v) => v,
+        None => return,
+    };
+
+    let mut entries = read_entries(&path);
+
+    push_entry(&mut entries, HistoryEntry {
+        query,
+        accepted: accepted.to_vec(),
+    });
+
+    let _ = write_entries(&path, &entries);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    fn entry(query: &str, accepted: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            query: query.to_owned(),
+            accepted: accepted.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let path = env::temp_dir()
+            .join(format!("sb-history-test-{}", process::id()))
+            .join(HISTORY_FILE);
+
+        let entries = [
+            entry("ls", &[]),
+            entry("git log", &["git", "gitk"]),
+            entry("-type:alias \"gi\"", &["gitk"]),
+        ];
+
+        write_entries(&path, &entries).unwrap();
+        let read = read_entries(&path);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(read, entries);
+    }
+
+    #[test]
+    fn entries_are_sanitized() {
+        let path = env::temp_dir().join(format!("sb-history-sanitize-{}", process::id()));
+
+        write_entries(&path, &[entry("a\tb\nc", &["d\re"])]).unwrap();
+        let read = read_entries(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(read, [entry("a b c", &["d e"])]);
+    }
+
+    #[test]
+    fn repeated_queries_move_to_the_end() {
+        let mut entries = vec![entry("ls", &["ls"]), entry("git", &[]), entry("vim", &[])];
+
+        push_entry(&mut entries, entry("ls", &["lsblk"]));
+
+        assert_eq!(entries, [entry("git", &[]), entry("vim", &[]), entry("ls", &["lsblk"])]);
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped_over_the_limit() {
+        let mut entries = (0..HISTORY_LIMIT)
+            .map(|i| entry(&i.to_string(), &[]))
+            .collect::<Vec<HistoryEntry>>();
+
+        push_entry(&mut entries, entry("new", &[]));
+
+        assert_eq!(entries.len(), HISTORY_LIMIT);
+        assert_eq!(entries.first(), Some(&entry("1", &[])));
+        assert_eq!(entries.last(), Some(&entry("new", &[])));
+    }
+}
@@ -0,0 +1,15 @@
+use crate::cli::InitShell;
+
+const BASH_SCRIPT: &str = include_str!("../shell/key-bindings.bash");
+const ZSH_SCRIPT: &str = include_str!("../shell/key-bindings.zsh");
+const FISH_SCRIPT: &str = include_str!("../shell/key-bindings.fish");
+
+pub fn print_init_script(shell: InitShell) {
+    let script = match shell {
+        InitShell::Bash => BASH_SCRIPT,
+        InitShell::Zsh => ZSH_SCRIPT,
+        InitShell::Fish => FISH_SCRIPT,
+    };
+
+    print!("{}", script);
+}
@@ -2,7 +2,8 @@ use std::{
     sync::{
         Arc,
         LazyLock,
-        atomic::{AtomicBool, Ordering},
+        OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
     time::{Duration, Instant},
 };
@@ -10,24 +11,40 @@ use std::{
 use color_eyre::eyre::Result as RepResult;
 
 use ratatui::{
-    DefaultTerminal, Frame,
-    crossterm::event::{self, Event, KeyEvent, poll},
-    layout::{Constraint, Direction, Layout},
+    Frame, Terminal,
+    backend::Backend,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind, poll},
+    layout::{Constraint, Direction, Layout, Position},
 };
 
-use tui_input::{Input, backend::crossterm::EventHandler};
+use tui_input::{Input, InputRequest, backend::crossterm::EventHandler};
 
 use crate::{
     app::App, binaries::{
+        BinSearchResult,
+        Binary,
         BinaryNode,
+        attach_details,
+        attach_file_infos,
         attach_manpaths,
-        search_binaries,
+        get_indexed_count,
     },
     cli::Cli,
+    clipboard::clipboard_copy,
+    frecency::record_pick,
+    history::{HistoryEntry, read_history, record_history},
+    keymap::{Action, InputMode, get_keymap},
+    query::Query,
+    searcher::Searcher,
     states::*,
+    pager::open_manpage,
+    runner::run_binary,
+    terminal::{clear_inline, init_stdout_terminal, init_tty_terminal, restore_terminal, resume_terminal},
     widgets::*
 };
 
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 const APP_ROOT_LAYOUT: LazyLock<Layout> = LazyLock::new(|| -> Layout {
     Layout::default()
         .direction(Direction::Horizontal)
@@ -44,27 +61,65 @@ const APP_LAYOUT: LazyLock<Layout> = LazyLock::new(|| -> Layout {
         .constraints([
             Constraint::Fill(1),
             Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+});
+
+static DETAILS_LAYOUT: LazyLock<Layout> = LazyLock::new(|| -> Layout {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(12),
         ])
 });
 
+enum PendingCommand {
+    OpenMan(BinaryNode),
+    Run(BinaryNode, String),
+}
+
+struct RunPrompt {
+    binary: BinaryNode,
+    title: String,
+    input: Input,
+}
+
 pub struct InteractiveApp {
     args: Option<Cli>,
 
     is_running: bool,
     redraw: Arc<AtomicBool>,
+    loading_descriptions: Arc<AtomicUsize>,
 
     input: Input,
+    searcher: Searcher,
     result: Option<BinaryListState>,
-    
+    result_value: String,
+    indexed: Arc<OnceLock<usize>>,
+    latency: Option<Duration>,
+    accepted: Vec<BinaryNode>,
+    history: Option<Vec<HistoryEntry>>,
+    history_index: Option<usize>,
+    show_details: bool,
+    show_descriptions: bool,
+    details_scroll: u16,
+    input_mode: InputMode,
+    pending: Option<PendingCommand>,
+    run_prompt: Option<RunPrompt>,
+    notice: Option<String>,
+
     #[cfg(debug_assertions)]
     tick_state: TickState,
     cursor_state: CursorState,
+    mouse_state: MouseState,
 }
 
 impl Default for InteractiveApp {
     fn default() -> Self {
         let redraw = Arc::new(AtomicBool::new(false));
         let input = Input::default().with_cursor(1).into();
+        let searcher = Searcher::new(redraw.clone());
 
         #[cfg(debug_assertions)]
         let tick_state = TickState::default();
@@ -74,11 +129,27 @@ impl Default for InteractiveApp {
             args: None,
             is_running: true,
             redraw,
+            loading_descriptions: Arc::new(AtomicUsize::new(0)),
             input,
+            searcher,
             result: None,
+            result_value: String::new(),
+            indexed: Arc::new(OnceLock::new()),
+            latency: None,
+            accepted: vec![],
+            history: None,
+            history_index: None,
+            show_details: false,
+            show_descriptions: false,
+            details_scroll: 0,
+            input_mode: InputMode::Insert,
+            pending: None,
+            run_prompt: None,
+            notice: None,
             #[cfg(debug_assertions)]
             tick_state,
             cursor_state,
+            mouse_state: MouseState::default(),
         }
     }
 }
@@ -88,7 +159,9 @@ impl App for InteractiveApp {
         let input_arg = args.input.clone();
 
         let mut self_ = Self::default();
+        self_.show_descriptions = args.show_descriptions;
         self_.args = Some(args);
+        self_.load_indexed_count();
 
         if let Some(v) = input_arg {
             let input = self_.input.with_value(v);
@@ -100,11 +173,54 @@ impl App for InteractiveApp {
     }
 
     fn run(&mut self) -> RepResult<()> {
-        let terminal = ratatui::init();
-        self.run_tui(terminal)
-            .map(|_| {
-                ratatui::restore();
-            })
+        let (use_tty, height) = match &self.args {
+            Some(args) => (args.use_tty, args.height),
+            None => (false, None),
+        };
+
+        let result = match use_tty {
+            true => init_tty_terminal(height).and_then(|(mut terminal, origin)| {
+                self.run_tui(&mut terminal)?;
+
+                if height.is_some() {
+                    clear_inline(&mut terminal, origin)?;
+                }
+
+                Ok(())
+            }),
+            false => init_stdout_terminal(height).and_then(|mut terminal| {
+                self.run_tui(&mut terminal)?;
+
+                if height.is_some() {
+                    clear_inline(&mut terminal, None)?;
+                }
+
+                Ok(())
+            }),
+        };
+
+        restore_terminal(use_tty, height.is_some());
+        result?;
+
+        let accepted_names = self.accepted
+            .iter()
+            .map(|binary| binary.read().unwrap().name.clone())
+            .collect::<Vec<String>>();
+
+        record_history(self.input.value(), &accepted_names);
+
+        for binary in &self.accepted {
+            let readable_binary = binary.read().unwrap();
+
+            record_pick(&readable_binary.name);
+            println!("{}", readable_binary.name);
+
+            if let Some(hint) = readable_binary.get_path_hint() {
+                eprintln!("{} is not on PATH, reach it with: {}", readable_binary.name, hint);
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -122,49 +238,127 @@ impl InteractiveApp {
         }
     }
 
-    #[cfg(debug_assertions)]
-    fn debug_tick(&self, frame: &mut Frame) {
-        use ratatui::text::Span;
+    fn get_history_title(&self) -> Option<String> {
+        let history = self.history.as_ref()?;
+        let index = self.history_index?;
+        let entry = history.get(index)?;
+
+        let mut title = format!("history {}/{}", index + 1, history.len());
 
-        let tps_panel_text = format!(
-            "Last TPS: {}",
-            self.tick_state.rate.to_string()
-        );
+        if !entry.accepted.is_empty() {
+            title += &format!(" → {}", entry.accepted.join(", "));
+        }
 
-        let tps_panel = Span::raw(tps_panel_text);
+        Some(title)
+    }
 
-        frame.render_widget(tps_panel, frame.area());
+    fn get_status_line(&self, query: &Query) -> StatusLine {
+        #[cfg(debug_assertions)]
+        let tick_rate = Some(self.tick_state.rate);
+        #[cfg(not(debug_assertions))]
+        let tick_rate = None;
+
+        let result = self.result.as_ref();
+
+        StatusLine {
+            indexed: self.indexed.get().copied(),
+            matched: result.map_or(0, |result| result.binaries.total),
+            selected: result
+                .filter(|result| !result.binaries.is_empty())
+                .map(|result| (result.selected, result.binaries.len())),
+            scoring: query.scoring,
+            latency: self.latency,
+            loading: self.loading_descriptions.load(Ordering::Acquire) > 0,
+            tick_rate,
+        }
     }
 
     fn draw(&mut self, frame: &mut Frame) {
-        let [_, root_area, _] = APP_ROOT_LAYOUT.areas(frame.area());
+        let full_width = self.args
+            .as_ref()
+            .is_some_and(|args| args.full_width);
+
+        let root_area = match full_width {
+            true => frame.area(),
+            false => APP_ROOT_LAYOUT.areas::<3>(frame.area())[1],
+        };
 
         let [
-            result_area,
+            mut result_area,
             input_area,
+            status_area,
         ] = APP_LAYOUT.areas(root_area);
 
+        self.mouse_state.input_area = input_area;
+        self.mouse_state.details_area = None;
+
+        if self.show_details {
+            let [list_area, details_area] = DETAILS_LAYOUT.areas(result_area);
+            result_area = list_area;
+            self.mouse_state.details_area = Some(details_area);
+
+            let targets = self.result
+                .as_ref()
+                .map(|result| result.get_targets())
+                .unwrap_or_default();
+
+            let readable_targets = targets
+                .iter()
+                .map(|binary| binary.read().unwrap())
+                .collect::<Vec<_>>();
+
+            let binaries = readable_targets
+                .iter()
+                .map(|binary| &**binary)
+                .collect::<Vec<&Binary>>();
+
+            let details_panel = DetailsPanel {
+                binaries: &binaries,
+                scroll: self.details_scroll,
+            };
+
+            frame.render_widget(details_panel, details_area);
+        }
+
+        let mode = get_keymap()
+            .has_normal_mode()
+            .then_some(self.input_mode);
+
+        let history_title = self.get_history_title();
+
+        let (inner, title) = match &self.run_prompt {
+            Some(prompt) => (&prompt.input, Some(prompt.title.as_str())),
+            None => (&self.input, history_title.as_deref().or(self.notice.as_deref())),
+        };
+
         let search_input = SearchInput {
-            inner: &self.input,
+            inner,
+            title,
+            mode,
             cursor_state: &mut self.cursor_state,
         };
 
+        let query = Query::parse(&self.result_value);
+
+        self.mouse_state.result_area = result_area;
+
         let search_result = SearchResult {
             binary_list: self.result.as_ref(),
+            item_areas: &mut self.mouse_state.item_areas,
+            query: &query,
+            show_descriptions: self.show_descriptions,
         };
 
         frame.render_widget(search_input, input_area);
         frame.render_widget(search_result, result_area);
+        frame.render_widget(self.get_status_line(&query), status_area);
 
         if let Some(cursor_pos) = self.cursor_state.position {
             frame.set_cursor_position(cursor_pos);
         }
-
-        #[cfg(debug_assertions)]
-        self.debug_tick(frame);
     }
 
-    fn run_tui(&mut self, mut terminal: DefaultTerminal) -> RepResult<()> {
+    fn run_tui<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> RepResult<()> {
         while self.is_running {
             #[cfg(debug_assertions)]
             self.count_tick();
@@ -174,11 +368,46 @@ impl InteractiveApp {
             if self.wait_event()? {
                 self.event_handler(event::read()?);
             }
+
+            if let Some(command) = self.pending.take() {
+                self.run_pending(terminal, command)?;
+            }
+
+            self.receive_search_result();
         }
 
         Ok(())
     }
 
+    fn run_pending<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        command: PendingCommand,
+    ) -> RepResult<()> {
+        let (use_tty, is_inline) = match &self.args {
+            Some(args) => (args.use_tty, args.height.is_some()),
+            None => (false, false),
+        };
+
+        restore_terminal(use_tty, is_inline);
+
+        match command {
+            PendingCommand::OpenMan(binary) => {
+                let readable_binary = binary.read().unwrap();
+
+                self.notice = open_manpage(&readable_binary, use_tty)
+                    .err()
+                    .map(|e| format!("{}: {}", readable_binary.name, e));
+            },
+            PendingCommand::Run(binary, args) => {
+                self.notice = run_binary(&binary.read().unwrap(), &args, use_tty).ok();
+            },
+        }
+
+        resume_terminal(terminal, use_tty, is_inline)?;
+        Ok(())
+    }
+
     fn exit(&mut self) {
         self.is_running = false;
     }
@@ -220,63 +449,437 @@ impl InteractiveApp {
         )
     }
 
+    fn get_incremental_base(&self, value: &str) -> Option<BinSearchResult> {
+        let incremental = self.args
+            .as_ref()
+            .is_some_and(|args| args.incremental);
+
+        if !incremental
+        || self.result_value.is_empty()
+        || !value.starts_with(self.result_value.as_str())
+        {
+            return None;
+        }
+
+        let binaries = &self.result.as_ref()?.binaries;
+
+        if binaries.is_truncated() {
+            return None;
+        }
+
+        Some(binaries.clone())
+    }
+
     fn search(&mut self) {
         let value = self.input.value();
 
-        if value.is_empty() {
-            self.result = None;
+        if Query::parse(value).is_empty() {
+            self.searcher.cancel();
+            self.result = self.result
+                .take()
+                .filter(|result| !result.marked.is_empty())
+                .map(|result| BinaryListState {
+                    binaries: BinSearchResult::default(),
+                    selected: 0,
+                    marked: result.marked,
+                });
+            self.result_value.clear();
             return;
         }
 
+        let base = self.get_incremental_base(value);
+        self.searcher.request(value.to_owned(), base);
+    }
+
+    fn receive_search_result(&mut self) {
+        let output = match self.searcher.take_result() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let marked = self.result
+            .take()
+            .map(|result| result.marked)
+            .unwrap_or_default();
+
         let result = BinaryListState {
-            binaries: search_binaries(value),
+            binaries: output.binaries,
             selected: 0,
+            marked,
         };
 
         self.result = Some(result);
+        self.result_value = output.value;
+        self.latency = Some(output.elapsed);
+
+        self.load_hot_binaries();
+    }
+
+    fn load_indexed_count(&self) {
+        let redraw_req = self.redraw.clone();
+        let indexed = self.indexed.clone();
+
+        rayon::spawn(move || {
+            indexed.get_or_init(get_indexed_count);
+            redraw_req.store(true, Ordering::Release);
+        });
     }
 
     fn add_descriptions(&self) {
         let redraw_req = self.redraw.clone();
+        let loading = self.loading_descriptions.clone();
         let hot_binaries = self.get_hot_binaries();
 
+        loading.fetch_add(1, Ordering::AcqRel);
+
         rayon::spawn(move || {
             attach_manpaths(&hot_binaries);
+            loading.fetch_sub(1, Ordering::AcqRel);
             redraw_req.store(true, Ordering::Release);
         });
     }
 
+    fn add_file_infos(&self) {
+        let redraw_req = self.redraw.clone();
+        let mut hot_binaries = self.get_hot_binaries();
+
+        if let Some(result) = &self.result {
+            hot_binaries.extend(result.marked.iter().cloned());
+        }
+
+        rayon::spawn(move || {
+            attach_file_infos(&hot_binaries);
+            attach_details(&hot_binaries);
+            redraw_req.store(true, Ordering::Release);
+        });
+    }
+
+    fn load_hot_binaries(&self) {
+        if self.show_descriptions {
+            self.add_descriptions();
+        }
+
+        if self.show_details {
+            self.add_file_infos();
+        }
+    }
+
+    fn toggle_details(&mut self) {
+        self.show_details = !self.show_details;
+        self.details_scroll = 0;
+        self.load_hot_binaries();
+    }
+
+    fn toggle_descriptions(&mut self) {
+        self.show_descriptions = !self.show_descriptions;
+        self.load_hot_binaries();
+    }
+
+    fn scroll_details(&mut self, offset: i16) {
+        self.details_scroll = self.details_scroll.saturating_add_signed(offset);
+    }
+
+    fn get_targets(&self) -> Vec<BinaryNode> {
+        self.result
+            .as_ref()
+            .map(|result| result.get_targets())
+            .unwrap_or_default()
+    }
+
+    fn copy_targets(&self) {
+        let names = self
+            .get_targets()
+            .iter()
+            .map(|binary| binary.read().unwrap().name.clone())
+            .collect::<Vec<String>>();
+
+        if !names.is_empty() {
+            let _ = clipboard_copy(&names.join("\n"));
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(result) = &mut self.result {
+            result.toggle_mark();
+        }
+
+        self.move_selection(1);
+    }
+
+    fn clear_marks(&mut self) {
+        if let Some(result) = &mut self.result {
+            result.clear_marks();
+        }
+    }
+
+    fn clear_input(&mut self) {
+        self.input.reset();
+        self.history_index = None;
+        self.handle_post_input();
+    }
+
+    fn browse_history(&mut self, offset: isize) {
+        let history = self.history.get_or_insert_with(read_history);
+
+        if history.is_empty() {
+            return;
+        }
+
+        let last = history.len() - 1;
+
+        let index = match self.history_index {
+            Some(index) => match index.checked_add_signed(offset) {
+                Some(v) => v,
+                None => return,
+            },
+            None if offset < 0 => last,
+            None => return,
+        };
+
+        match history.get(index) {
+            Some(entry) => {
+                self.input = Input::new(entry.query.clone());
+                self.history_index = Some(index);
+            },
+            None => {
+                self.input.reset();
+                self.history_index = None;
+            },
+        }
+
+        self.handle_post_input();
+    }
+
+    fn move_up(&mut self) {
+        let is_at_top = self.result
+            .as_ref()
+            .is_none_or(|result| result.selected == 0);
+
+        match is_at_top && (self.input.value().is_empty() || self.history_index.is_some()) {
+            true => self.browse_history(-1),
+            false => self.move_selection(-1),
+        }
+    }
+
+    fn move_cursor(&mut self, request: InputRequest) {
+        self.input.handle(request);
+    }
+
     fn handle_post_input(&mut self) {
         self.search();
-    
-        let args = match &self.args {
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let result = match &mut self.result {
             Some(v) => v,
             None => return,
         };
 
-        if args.show_descriptions {
-            self.add_descriptions();
+        let last = result.binaries.len().saturating_sub(1);
+        let selected = result.selected.saturating_add_signed(offset);
+
+        result.selected = selected.min(last);
+        self.details_scroll = 0;
+
+        self.load_hot_binaries();
+    }
+
+    fn open_man(&mut self) {
+        let selected = self.result
+            .as_ref()
+            .and_then(|result| result.get_selected())
+            .cloned();
+
+        if let Some(binary) = selected {
+            self.pending = Some(PendingCommand::OpenMan(binary));
+        }
+    }
+
+    fn open_run_prompt(&mut self) {
+        let selected = self.result
+            .as_ref()
+            .and_then(|result| result.get_selected())
+            .cloned();
+
+        if let Some(binary) = selected {
+            let title = format!("run {}", binary.read().unwrap().name);
+
+            self.run_prompt = Some(RunPrompt {
+                binary,
+                title,
+                input: Input::default(),
+            });
+        }
+    }
+
+    fn run_prompt_handler(&mut self, event: &Event) {
+        let prompt = match &mut self.run_prompt {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Event::Key(e) = event {
+            match e.code {
+                KeyCode::Esc => {
+                    self.run_prompt = None;
+                    return;
+                },
+                KeyCode::Enter => {
+                    if let Some(prompt) = self.run_prompt.take() {
+                        let args = prompt.input.value().to_owned();
+                        self.pending = Some(PendingCommand::Run(prompt.binary, args));
+                    }
+
+                    return;
+                },
+                _ => {},
+            }
+        }
+
+        prompt.input.handle_event(event);
+    }
+
+    fn place_cursor(&mut self, position: Position) {
+        let area = self.mouse_state.input_area;
+        let width = area.width.saturating_sub(4);
+        let scroll = self.input.visual_scroll(width as usize);
+        let column = position.x.saturating_sub(area.x + 2) as usize;
+
+        self.move_cursor(InputRequest::SetCursor(scroll + column));
+    }
+
+    fn click(&mut self, position: Position) {
+        if self.mouse_state.input_area.contains(position) {
+            self.place_cursor(position);
+            return;
+        }
+
+        let now = Instant::now();
+        let double_clicked = self.mouse_state.last_click
+            .take()
+            .filter(|(time, last, _)| *last == position && now - *time < DOUBLE_CLICK_INTERVAL);
+
+        // The clicked item scrolls to the top on the first click,
+        // so a second click on the same spot accepts that item alone,
+        // leaving any marks out.
+        if let Some((_, _, binary)) = double_clicked {
+            self.accepted = vec![binary];
+            self.exit();
+            return;
+        }
+
+        let index = self.mouse_state.item_areas
+            .iter()
+            .position(|area| area.contains(position));
+
+        let index = match index {
+            Some(v) => v,
+            None => return,
+        };
+
+        self.move_selection(index as isize);
+
+        let clicked = self.result
+            .as_ref()
+            .and_then(|result| result.get_selected())
+            .cloned();
+
+        if let Some(binary) = clicked {
+            self.mouse_state.last_click = Some((now, position, binary));
         }
     }
 
-    fn key_event_handler(&mut self, event: KeyEvent) {
-        match event.code {
-            event::KeyCode::Esc => self.exit(),
-            event::KeyCode::Up => {},
-            event::KeyCode::Down => {},
+    fn scroll(&mut self, position: Position, offset: isize) {
+        if self.mouse_state.details_area.is_some_and(|area| area.contains(position)) {
+            self.scroll_details(offset as i16);
+        } else if self.mouse_state.result_area.contains(position) {
+            self.move_selection(offset);
+        }
+    }
+
+    fn mouse_event_handler(&mut self, event: &MouseEvent) {
+        let position = Position::new(event.column, event.row);
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.click(position),
+            MouseEventKind::ScrollUp => self.scroll(position, -1),
+            MouseEventKind::ScrollDown => self.scroll(position, 1),
             _ => {},
         }
     }
 
+    fn accept(&mut self) {
+        let targets = self.get_targets();
+
+        if !targets.is_empty() {
+            self.accepted = targets;
+            self.exit();
+        }
+    }
+
+    fn action_handler(&mut self, action: Action) {
+        match action {
+            Action::Exit => self.exit(),
+            Action::Accept => self.accept(),
+            Action::Copy => self.copy_targets(),
+            Action::ToggleDetails => self.toggle_details(),
+            Action::ToggleDescriptions => self.toggle_descriptions(),
+            Action::OpenMan => self.open_man(),
+            Action::RunPrompt => self.open_run_prompt(),
+            Action::HistoryPrev => self.browse_history(-1),
+            Action::HistoryNext => self.browse_history(1),
+            Action::MoveUp => self.move_up(),
+            Action::MoveDown => self.move_selection(1),
+            Action::ToggleMark => self.toggle_mark(),
+            Action::ClearMarks => self.clear_marks(),
+            Action::PreviewScrollUp => self.scroll_details(-1),
+            Action::PreviewScrollDown => self.scroll_details(1),
+            Action::ClearInput => self.clear_input(),
+            Action::CursorLeft => self.move_cursor(InputRequest::GoToPrevChar),
+            Action::CursorRight => self.move_cursor(InputRequest::GoToNextChar),
+            Action::CursorStart => self.move_cursor(InputRequest::GoToStart),
+            Action::CursorEnd => self.move_cursor(InputRequest::GoToEnd),
+            Action::InsertMode => self.input_mode = InputMode::Insert,
+            Action::NormalMode => self.input_mode = InputMode::Normal,
+        }
+    }
+
+    fn key_event_handler(&mut self, event: &KeyEvent) -> bool {
+        match get_keymap().get_action(self.input_mode, event) {
+            Some(action) => {
+                self.action_handler(action);
+                true
+            },
+            None => false,
+        }
+    }
+
     fn event_handler(&mut self, event: Event) {
-        if let Some(_) = self.input.handle_event(&event) {
-            self.handle_post_input();
+        if let Event::Key(_) = &event {
+            self.notice = None;
+        }
+
+        if self.run_prompt.is_some() {
+            self.run_prompt_handler(&event);
             return;
         }
 
-        match event {
-            Event::Key(e) => self.key_event_handler(e),
-            _ => {}
+        if let Event::Mouse(e) = &event {
+            self.mouse_event_handler(e);
+            return;
+        }
+
+        if let Event::Key(e) = &event
+        && (self.key_event_handler(e) || self.input_mode == InputMode::Normal)
+        {
+            return;
+        }
+
+        if let Some(state) = self.input.handle_event(&event)
+        && state.value
+        {
+            self.history_index = None;
+            self.handle_post_input();
         }
     }
 
@@ -0,0 +1,85 @@
+use serde_json::{Map, Value, json};
+
+use crate::{
+    binaries::{Binary, BinaryKind},
+    descriptions::Description,
+    filetype::{FileInfo, FileType},
+};
+
+pub fn file_type_to_json(file_type: &FileType) -> Value {
+    let mut object = Map::new();
+    object.insert("type".into(), json!(file_type.get_name()));
+
+    match file_type {
+        FileType::Elf { arch, linking } => {
+            object.insert("arch".into(), json!(arch));
+            object.insert("linking".into(), json!(linking.to_string()));
+        },
+        FileType::Script { interpreter } => {
+            object.insert("interpreter".into(), json!(interpreter));
+        },
+        FileType::AppImage { arch } => {
+            object.insert("arch".into(), json!(arch));
+        },
+        FileType::Flatpak { app_id } => {
+            object.insert("app_id".into(), json!(app_id));
+        },
+        FileType::Unknown => {},
+    }
+
+    Value::Object(object)
+}
+
+fn file_info_to_json(object: &mut Map<String, Value>, info: &FileInfo) {
+    object.insert("file_type".into(), file_type_to_json(&info.file_type));
+
+    if let Some(target) = &info.symlink_target {
+        object.insert("symlink_target".into(), json!(target));
+    }
+
+    if let Some(package) = &info.package {
+        object.insert("package".into(), json!({
+            "name": package.name,
+            "version": package.version,
+            "manager": package.manager,
+        }));
+    }
+}
+
+pub fn binary_to_json(binary: &Binary, description: Option<&Description>) -> Value {
+    let mut object = Map::new();
+
+    object.insert("name".into(), json!(binary.name));
+    object.insert("kind".into(), json!(binary.kind.marker().unwrap_or("executable")));
+
+    if binary.kind == BinaryKind::Executable {
+        object.insert("path".into(), json!(binary.path));
+    }
+
+    if binary.off_path {
+        object.insert("off_path".into(), json!(true));
+        object.insert("hint".into(), json!(binary.get_path_hint()));
+    }
+
+    if let Some(info) = &binary.file_info {
+        file_info_to_json(&mut object, info);
+    }
+
+    if !binary.aliases.is_empty() {
+        object.insert("aliases".into(), json!(binary.aliases));
+    }
+
+    if let Some(multi_call) = &binary.multi_call {
+        object.insert("multi_call".into(), json!(multi_call));
+    }
+
+    if let Some(app) = binary.apps.first() {
+        object.insert("app_name".into(), json!(app.name));
+    }
+
+    if let Some(desc) = description {
+        object.insert("description".into(), json!(desc.value));
+    }
+
+    Value::Object(object)
+}
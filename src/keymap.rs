@@ -0,0 +1,304 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Exit,
+    Accept,
+    Copy,
+    ToggleDetails,
+    ToggleDescriptions,
+    OpenMan,
+    RunPrompt,
+    HistoryPrev,
+    HistoryNext,
+    MoveUp,
+    MoveDown,
+    ToggleMark,
+    ClearMarks,
+    PreviewScrollUp,
+    PreviewScrollDown,
+    ClearInput,
+    CursorLeft,
+    CursorRight,
+    CursorStart,
+    CursorEnd,
+    InsertMode,
+    NormalMode,
+}
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "exit" => Some(Self::Exit),
+            "accept" => Some(Self::Accept),
+            "copy" => Some(Self::Copy),
+            "toggle-details" => Some(Self::ToggleDetails),
+            "toggle-descriptions" => Some(Self::ToggleDescriptions),
+            "open-man" => Some(Self::OpenMan),
+            "run-prompt" => Some(Self::RunPrompt),
+            "history-prev" => Some(Self::HistoryPrev),
+            "history-next" => Some(Self::HistoryNext),
+            "move-up" => Some(Self::MoveUp),
+            "move-down" => Some(Self::MoveDown),
+            "toggle-mark" => Some(Self::ToggleMark),
+            "clear-marks" => Some(Self::ClearMarks),
+            "preview-scroll-up" => Some(Self::PreviewScrollUp),
+            "preview-scroll-down" => Some(Self::PreviewScrollDown),
+            "clear-input" => Some(Self::ClearInput),
+            "cursor-left" => Some(Self::CursorLeft),
+            "cursor-right" => Some(Self::CursorRight),
+            "cursor-start" => Some(Self::CursorStart),
+            "cursor-end" => Some(Self::CursorEnd),
+            "insert-mode" => Some(Self::InsertMode),
+            "normal-mode" => Some(Self::NormalMode),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    Insert,
+    Normal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapPreset {
+    Default,
+    Emacs,
+    Vim,
+}
+
+impl KeymapPreset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::Default),
+            "emacs" => Some(Self::Emacs),
+            "vim" => Some(Self::Vim),
+            _ => None,
+        }
+    }
+}
+
+const DEFAULT_BINDINGS: &[(&str, Action)] = &[
+    ("esc", Action::Exit),
+    ("enter", Action::Accept),
+    ("tab", Action::ToggleDetails),
+    ("up", Action::MoveUp),
+    ("down", Action::MoveDown),
+    ("ctrl-space", Action::ToggleMark),
+    ("alt-u", Action::ClearMarks),
+    ("ctrl-y", Action::Copy),
+    ("ctrl-t", Action::ToggleDescriptions),
+    ("ctrl-o", Action::OpenMan),
+    ("alt-r", Action::RunPrompt),
+    ("ctrl-r", Action::HistoryPrev),
+    ("ctrl-s", Action::HistoryNext),
+    ("pageup", Action::PreviewScrollUp),
+    ("pagedown", Action::PreviewScrollDown),
+    ("ctrl-l", Action::ClearInput),
+];
+
+const EMACS_BINDINGS: &[(&str, Action)] = &[
+    ("ctrl-g", Action::Exit),
+    ("ctrl-j", Action::Accept),
+    ("ctrl-p", Action::MoveUp),
+    ("ctrl-n", Action::MoveDown),
+    ("alt-w", Action::Copy),
+    ("alt-v", Action::PreviewScrollUp),
+    ("ctrl-v", Action::PreviewScrollDown),
+];
+
+const VIM_INSERT_BINDINGS: &[(&str, Action)] = &[
+    ("esc", Action::NormalMode),
+    ("ctrl-c", Action::Exit),
+    ("ctrl-p", Action::MoveUp),
+    ("ctrl-n", Action::MoveDown),
+];
+
+const VIM_NORMAL_BINDINGS: &[(&str, Action)] = &[
+    ("esc", Action::Exit),
+    ("q", Action::Exit),
+    ("ctrl-c", Action::Exit),
+    ("enter", Action::Accept),
+    ("tab", Action::ToggleDetails),
+    ("j", Action::MoveDown),
+    ("k", Action::MoveUp),
+    ("down", Action::MoveDown),
+    ("up", Action::MoveUp),
+    ("m", Action::ToggleMark),
+    ("space", Action::ToggleMark),
+    ("M", Action::ClearMarks),
+    ("y", Action::Copy),
+    ("d", Action::ToggleDescriptions),
+    ("K", Action::OpenMan),
+    ("!", Action::RunPrompt),
+    ("ctrl-u", Action::PreviewScrollUp),
+    ("ctrl-d", Action::PreviewScrollDown),
+    ("S", Action::ClearInput),
+    ("h", Action::CursorLeft),
+    ("l", Action::CursorRight),
+    ("0", Action::CursorStart),
+    ("$", Action::CursorEnd),
+    ("i", Action::InsertMode),
+    ("a", Action::InsertMode),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shifted characters already arrive as their upper case form,
+        // so the modifier is only kept for named keys.
+        let modifiers = match code {
+            KeyCode::Char(_) => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+
+        Self { code, modifiers }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = value;
+
+        loop {
+            let (modifier, rest) = match key.split_once('-') {
+                Some(v) if !v.1.is_empty() => v,
+                _ => break,
+            };
+
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+
+            key = rest;
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            _ => {
+                let mut chars = key.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            },
+        };
+
+        Some(Self::new(code, modifiers))
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(value: &KeyEvent) -> Self {
+        Self::new(value.code, value.modifiers)
+    }
+}
+
+type Bindings = HashMap<KeyBinding, Action>;
+
+fn add_bindings(bindings: &mut Bindings, defaults: &[(&str, Action)]) {
+    for (key, action) in defaults {
+        if let Some(binding) = KeyBinding::parse(key) {
+            bindings.insert(binding, *action);
+        }
+    }
+}
+
+pub struct Keymap {
+    insert: Bindings,
+    normal: Option<Bindings>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_preset(KeymapPreset::Default)
+    }
+}
+
+impl Keymap {
+    pub fn from_preset(preset: KeymapPreset) -> Self {
+        let mut insert = Bindings::new();
+        add_bindings(&mut insert, DEFAULT_BINDINGS);
+
+        let normal = match preset {
+            KeymapPreset::Default => None,
+            KeymapPreset::Emacs => {
+                add_bindings(&mut insert, EMACS_BINDINGS);
+                None
+            },
+            KeymapPreset::Vim => {
+                let mut normal = Bindings::new();
+
+                add_bindings(&mut insert, VIM_INSERT_BINDINGS);
+                add_bindings(&mut normal, VIM_NORMAL_BINDINGS);
+
+                Some(normal)
+            },
+        };
+
+        Self { insert, normal }
+    }
+
+    pub fn has_normal_mode(&self) -> bool {
+        self.normal.is_some()
+    }
+
+    pub fn bind(&mut self, mode: InputMode, binding: KeyBinding, action: Action) {
+        let bindings = match mode {
+            InputMode::Insert => &mut self.insert,
+            InputMode::Normal => match &mut self.normal {
+                Some(v) => v,
+                None => return,
+            },
+        };
+
+        bindings.insert(binding, action);
+    }
+
+    pub fn get_action(&self, mode: InputMode, event: &KeyEvent) -> Option<Action> {
+        let bindings = match mode {
+            InputMode::Insert => &self.insert,
+            InputMode::Normal => self.normal.as_ref()?,
+        };
+
+        bindings
+            .get(&KeyBinding::from(event))
+            .copied()
+    }
+}
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+pub fn init_keymap(keymap: Keymap) {
+    KEYMAP.get_or_init(|| keymap);
+}
+
+pub fn get_keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
@@ -3,22 +3,44 @@ compile_error!("No Win");
 
 mod clipboard;
 mod cli;
+mod config;
+mod xdg;
+mod root;
+mod frecency;
+mod history;
 mod man;
 mod roff;
+mod filetype;
+mod details;
+mod packages;
+mod json;
+mod query;
 mod binaries;
+mod shell;
+mod desktop;
+mod toolchains;
 mod descriptions;
+mod theme;
+mod keymap;
+mod daemon;
+mod searcher;
 mod widgets;
 mod states;
+mod terminal;
+mod pager;
+mod runner;
+mod init;
+mod not_found;
 mod app;
 mod simple_app;
 mod interactive_app;
+mod daemon_app;
 
-use clap::Parser;
 use color_eyre::eyre::Result as RepResult;
 
 #[cfg(target_os = "linux")]
 use clipboard::handle_clipboard_request;
-use cli::Cli;
+use config::load_args;
 
 use app::run_app;
 
@@ -26,5 +48,5 @@ fn main() -> RepResult<()> {
     #[cfg(target_os = "linux")]
     let _ = handle_clipboard_request();
 
-    run_app(Cli::parse())
+    run_app(load_args()?)
 }
@@ -1,13 +1,18 @@
 use std::{
-    fs::File,
+    collections::HashMap,
+    env,
+    fs::{self, File},
     io::{self, BufRead, Cursor, Read},
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
     string::FromUtf8Error,
+    sync::LazyLock,
 };
 
 use flate2::read::GzDecoder;
 
+use crate::root::{canonicalize, get_root_dir, rooted};
+
 #[derive(Debug, PartialEq)]
 pub enum ManpageType {
     Man,
@@ -54,7 +59,7 @@ impl TryFrom<&Path> for ManpageBuffer {
             None => false,
         };
 
-        let file = File::open(path)?;
+        let file = File::open(canonicalize(path)?)?;
 
         if !is_gz {
             return Ok(ManpageBuffer::from(file));
@@ -104,9 +109,21 @@ pub struct Manpaths {
     pub not_founds: Vec<String>,
 }
 
-pub fn get_manpaths<'a>(names: Vec<String>) -> Option<Manpaths> {
+pub fn get_man_command() -> Command {
     let mut man_command = Command::new("man");
 
+    if get_root_dir().is_some()
+    && let Ok(dirs) = env::join_paths(MANPATH_DIRS.iter())
+    {
+        man_command.arg("-M").arg(dirs);
+    }
+
+    man_command
+}
+
+pub fn get_manpaths<'a>(names: Vec<String>) -> Option<Manpaths> {
+    let mut man_command = get_man_command();
+
     let man = man_command
         .arg("-w")
         .args(names)
@@ -159,3 +176,160 @@ pub fn get_manpaths<'a>(names: Vec<String>) -> Option<Manpaths> {
 
     Some(manpaths)
 }
+
+const DEFAULT_MANPATH: &str = "/usr/local/share/man:/usr/share/man";
+const MANPAGE_COMPRESSIONS: [&str; 4] = ["gz", "bz2", "xz", "zst"];
+const MANPATH_CONFIGS: [&str; 2] = ["/etc/manpath.config", "/etc/man_db.conf"];
+
+type ManSections = HashMap<String, Vec<String>>;
+
+static MANPATH_DIRS: LazyLock<Vec<PathBuf>> = LazyLock::new(get_manpath_dirs);
+static MAN_SECTIONS: LazyLock<ManSections> = LazyLock::new(init_man_sections);
+
+fn get_configured_manpath() -> Option<String> {
+    let mut dirs: Vec<&str> = Vec::new();
+
+    let contents = MANPATH_CONFIGS
+        .iter()
+        .filter_map(|config| fs::read_to_string(rooted(Path::new(config))).ok())
+        .collect::<Vec<String>>();
+
+    for line in contents.iter().flat_map(|content| content.lines()) {
+        let mut fields = line.split_whitespace();
+
+        let dir = match fields.next() {
+            Some("MANDATORY_MANPATH") => fields.next(),
+            Some("MANPATH_MAP") => fields.nth(1),
+            _ => None,
+        };
+
+        if let Some(dir) = dir
+        && !dirs.contains(&dir)
+        {
+            dirs.push(dir);
+        }
+    }
+
+    match dirs.is_empty() {
+        true => None,
+        false => Some(dirs.join(":")),
+    }
+}
+
+fn get_root_manpath_dirs() -> Vec<PathBuf> {
+    let manpath = get_configured_manpath()
+        .unwrap_or(DEFAULT_MANPATH.to_owned());
+
+    env::split_paths(&manpath)
+        .map(|dir| rooted(&dir))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn get_manpath_dirs() -> Vec<PathBuf> {
+    if get_root_dir().is_some() {
+        return get_root_manpath_dirs();
+    }
+
+    let output = Command::new("manpath")
+        .arg("-q")
+        .stderr(Stdio::null())
+        .output();
+
+    let manpath = match output {
+        Ok(v) if v.status.success() => String::from_utf8(v.stdout).ok(),
+        _ => None,
+    };
+
+    let manpath = manpath
+        .or_else(|| env::var("MANPATH").ok())
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or(DEFAULT_MANPATH.to_owned());
+
+    env::split_paths(manpath.trim())
+        .collect()
+}
+
+fn get_page_name(file_name: &str) -> Option<&str> {
+    let name = match file_name.rsplit_once('.') {
+        Some((rest, ext)) if MANPAGE_COMPRESSIONS.contains(&ext) => rest,
+        _ => file_name,
+    };
+
+    name.rsplit_once('.').map(|(name, _)| name)
+}
+
+fn init_man_sections() -> ManSections {
+    let mut sections = ManSections::new();
+
+    let section_dirs = MANPATH_DIRS
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .flatten();
+
+    for section_dir in section_dirs {
+        let dir_name = section_dir.file_name();
+
+        let section = match dir_name.to_string_lossy().strip_prefix("man") {
+            Some(v) => v.to_owned(),
+            None => continue,
+        };
+
+        let pages = match fs::read_dir(section_dir.path()) {
+            Ok(v) => v.flatten(),
+            Err(_) => continue,
+        };
+
+        for page in pages {
+            let file_name = page.file_name();
+            let file_name = file_name.to_string_lossy();
+
+            let name = match get_page_name(&file_name) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let page_sections = sections
+                .entry(name.to_owned())
+                .or_default();
+
+            if !page_sections.contains(&section) {
+                page_sections.push(section.clone());
+            }
+        }
+    }
+
+    sections
+}
+
+pub fn get_man_sections(name: &str) -> Option<&'static Vec<String>> {
+    MAN_SECTIONS.get(name)
+}
+
+pub fn find_manpage(name: &str) -> Option<PathBuf> {
+    let sections = get_man_sections(name)?;
+
+    for section in sections {
+        let section_dirs = MANPATH_DIRS
+            .iter()
+            .map(|dir| dir.join(format!("man{}", section)));
+
+        for section_dir in section_dirs {
+            let pages = match fs::read_dir(&section_dir) {
+                Ok(v) => v.flatten(),
+                Err(_) => continue,
+            };
+
+            for page in pages {
+                let file_name = page.file_name();
+
+                if get_page_name(&file_name.to_string_lossy()) == Some(name) {
+                    return Some(page.path());
+                }
+            }
+        }
+    }
+
+    None
+}
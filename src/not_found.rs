@@ -0,0 +1,114 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    os::unix::process::CommandExt,
+    process,
+};
+
+use color_eyre::eyre::Result as RepResult;
+
+use crate::{
+    binaries::{BinaryKind, BinaryNode, SearchLimits, search_binaries},
+    query::{Query, Scoring},
+};
+
+const MIN_CONFIDENCE: f64 = 0.5;
+const SUGGESTION_LENGTH: usize = 5;
+const AMBIGUITY_GAP: f64 = 0.1;
+const NOT_FOUND_STATUS: i32 = 127;
+
+pub fn get_not_found_limits() -> SearchLimits {
+    SearchLimits {
+        min_score: MIN_CONFIDENCE,
+        length: Some(SUGGESTION_LENGTH),
+    }
+}
+
+fn get_suggestions(command: &str) -> Vec<(BinaryNode, f64)> {
+    let query = Query {
+        terms: vec![command.to_owned()],
+        ..Default::default()
+    };
+
+    let query = query.with_scoring(Scoring::Typo);
+
+    search_binaries(&query)
+        .owned_ordered_iter()
+        .map(|binary| {
+            let confidence = query.similarity(&binary.read().unwrap());
+            (binary, confidence)
+        })
+        .collect()
+}
+
+fn get_runnable(suggestions: &[(BinaryNode, f64)]) -> Option<&BinaryNode> {
+    let (top, top_confidence) = suggestions.first()?;
+
+    let other_confidence = suggestions[1..]
+        .iter()
+        .map(|(_, confidence)| *confidence)
+        .fold(0.0, f64::max);
+
+    let is_unambiguous = top_confidence - other_confidence >= AMBIGUITY_GAP;
+    let is_executable = top.read().unwrap().kind == BinaryKind::Executable;
+
+    (is_unambiguous && is_executable).then_some(top)
+}
+
+fn confirm(prompt: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+
+    eprint!("{} [y/N] ", prompt);
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+
+    match io::stdin().read_line(&mut answer) {
+        Ok(_) => matches!(answer.trim(), "y" | "Y" | "yes"),
+        Err(_) => false,
+    }
+}
+
+pub fn run_not_found(command: &str, args: &[String]) -> RepResult<()> {
+    eprintln!("sb: command not found: {}", command);
+
+    let suggestions = get_suggestions(command);
+
+    if suggestions.is_empty() {
+        process::exit(NOT_FOUND_STATUS);
+    }
+
+    eprintln!("Did you mean:");
+
+    for (binary, _) in &suggestions {
+        let readable_binary = binary.read().unwrap();
+
+        match readable_binary.get_marker() {
+            Some(marker) => eprintln!("    {} [{}]", readable_binary.name, marker),
+            None => eprintln!("    {}", readable_binary.name),
+        }
+    }
+
+    let runnable = match get_runnable(&suggestions) {
+        Some(v) => v.read().unwrap(),
+        None => process::exit(NOT_FOUND_STATUS),
+    };
+
+    let command_line = [runnable.name.as_str()]
+        .into_iter()
+        .chain(args.iter().map(|arg| arg.as_str()))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    if !confirm(&format!("Run \"{}\"?", command_line)) {
+        process::exit(NOT_FOUND_STATUS);
+    }
+
+    let error = process::Command::new(&runnable.path)
+        .arg0(&runnable.name)
+        .args(args)
+        .exec();
+
+    Err(error.into())
+}
@@ -0,0 +1,351 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+};
+
+use crate::root::{canonicalize, rooted};
+
+const DPKG_INFO_DIR: &str = "/var/lib/dpkg/info";
+const DPKG_STATUS: &str = "/var/lib/dpkg/status";
+const DPKG_LIST_EXTENSION: &str = "list";
+
+const PACMAN_LOCAL_DIR: &str = "/var/lib/pacman/local";
+
+#[cfg(feature = "rpm")]
+const RPM_SQLITE_DB: &str = "/var/lib/rpm/rpmdb.sqlite";
+
+const BIN_DIR_MARKER: &str = "bin/";
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    pub manager: &'static str,
+}
+
+impl fmt::Display for Package {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ({})", self.name, self.version, self.manager)
+    }
+}
+
+type PackageOwners = HashMap<PathBuf, Arc<Package>>;
+
+static PACKAGE_OWNERS: LazyLock<PackageOwners> = LazyLock::new(init_package_owners);
+
+#[derive(Default)]
+struct OwnersBuilder {
+    owners: PackageOwners,
+    dirs: HashMap<PathBuf, PathBuf>,
+}
+
+impl OwnersBuilder {
+    fn insert(&mut self, path: &Path, package: &Arc<Package>) {
+        let (dir, name) = match (path.parent(), path.file_name()) {
+            (Some(d), Some(n)) => (d, n),
+            _ => return,
+        };
+
+        let dir = self.dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| canonicalize(dir).unwrap_or(dir.to_path_buf()));
+
+        self.owners
+            .entry(dir.join(name))
+            .or_insert_with(|| package.clone());
+    }
+}
+
+fn get_dpkg_versions() -> HashMap<String, String> {
+    let content = fs::read_to_string(rooted(Path::new(DPKG_STATUS))).unwrap_or_default();
+    let mut versions = HashMap::new();
+
+    for stanza in content.split("\n\n") {
+        let mut name = None;
+        let mut version = None;
+
+        for line in stanza.lines() {
+            if let Some(v) = line.strip_prefix("Package: ") {
+                name = Some(v.trim());
+            } else if let Some(v) = line.strip_prefix("Version: ") {
+                version = Some(v.trim());
+            }
+        }
+
+        if let (Some(n), Some(v)) = (name, version) {
+            versions.insert(n.to_owned(), v.to_owned());
+        }
+    }
+
+    versions
+}
+
+fn add_dpkg_owners(builder: &mut OwnersBuilder) {
+    let lists = match fs::read_dir(rooted(Path::new(DPKG_INFO_DIR))) {
+        Ok(v) => v.flatten(),
+        Err(_) => return,
+    };
+
+    let versions = get_dpkg_versions();
+
+    for list in lists {
+        let path = list.path();
+
+        if path.extension().is_none_or(|ext| ext != DPKG_LIST_EXTENSION) {
+            continue;
+        }
+
+        let stem = match path.file_stem() {
+            Some(v) => v.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        let name = stem
+            .split_once(':')
+            .map(|(name, _)| name.to_owned())
+            .unwrap_or(stem);
+
+        let package = Arc::new(Package {
+            version: versions.get(&name).cloned().unwrap_or_default(),
+            name,
+            manager: "dpkg",
+        });
+
+        let content = fs::read_to_string(&path).unwrap_or_default();
+
+        for line in content.lines().filter(|l| l.contains(BIN_DIR_MARKER)) {
+            builder.insert(&rooted(Path::new(line)), &package);
+        }
+    }
+}
+
+fn parse_pacman_section<'a>(content: &'a str, section: &str) -> impl Iterator<Item = &'a str> {
+    content
+        .lines()
+        .skip_while(move |line| *line != section)
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+}
+
+fn add_pacman_owners(builder: &mut OwnersBuilder) {
+    let entries = match fs::read_dir(rooted(Path::new(PACMAN_LOCAL_DIR))) {
+        Ok(v) => v.flatten(),
+        Err(_) => return,
+    };
+
+    for entry in entries {
+        let dir = entry.path();
+
+        let desc = fs::read_to_string(dir.join("desc")).unwrap_or_default();
+        let files = fs::read_to_string(dir.join("files")).unwrap_or_default();
+
+        let name = parse_pacman_section(&desc, "%NAME%").next();
+        let version = parse_pacman_section(&desc, "%VERSION%").next();
+
+        let package = match (name, version) {
+            (Some(n), Some(v)) => Arc::new(Package {
+                name: n.to_owned(),
+                version: v.to_owned(),
+                manager: "pacman",
+            }),
+            _ => continue,
+        };
+
+        let paths = parse_pacman_section(&files, "%FILES%")
+            .filter(|line| line.contains(BIN_DIR_MARKER));
+
+        for path in paths {
+            builder.insert(&rooted(&Path::new("/").join(path)), &package);
+        }
+    }
+}
+
+#[cfg(feature = "rpm")]
+mod rpm {
+    use std::path::Path;
+
+    const TAG_NAME: u32 = 1000;
+    const TAG_VERSION: u32 = 1001;
+    const TAG_RELEASE: u32 = 1002;
+    const TAG_DIRINDEXES: u32 = 1116;
+    const TAG_BASENAMES: u32 = 1117;
+    const TAG_DIRNAMES: u32 = 1118;
+
+    const TYPE_INT32: u32 = 4;
+
+    pub struct RpmHeader<'a> {
+        entries: Vec<(u32, u32, usize, usize)>,
+        data: &'a [u8],
+    }
+
+    fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(u32::from_be_bytes(bytes))
+    }
+
+    impl<'a> RpmHeader<'a> {
+        pub fn parse(blob: &'a [u8]) -> Option<Self> {
+            let index_len = read_u32(blob, 0)? as usize;
+            let data_len = read_u32(blob, 4)? as usize;
+
+            let data_start = 8 + index_len * 16;
+            let data = blob.get(data_start..data_start + data_len)?;
+
+            let entries = (0..index_len)
+                .map(|i| {
+                    let offset = 8 + i * 16;
+                    Some((
+                        read_u32(blob, offset)?,
+                        read_u32(blob, offset + 4)?,
+                        read_u32(blob, offset + 8)? as usize,
+                        read_u32(blob, offset + 12)? as usize,
+                    ))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(Self { entries, data })
+        }
+
+        fn get_entry(&self, tag: u32) -> Option<&(u32, u32, usize, usize)> {
+            self.entries.iter().find(|(t, ..)| *t == tag)
+        }
+
+        fn get_strings(&self, tag: u32) -> Vec<&'a str> {
+            let (_, _, offset, count) = match self.get_entry(tag) {
+                Some(v) => *v,
+                None => return vec![],
+            };
+
+            self.data
+                .get(offset..)
+                .unwrap_or_default()
+                .split(|b| *b == 0)
+                .take(count)
+                .filter_map(|s| std::str::from_utf8(s).ok())
+                .collect()
+        }
+
+        fn get_string(&self, tag: u32) -> Option<&'a str> {
+            self.get_strings(tag).into_iter().next()
+        }
+
+        fn get_int32s(&self, tag: u32) -> Vec<u32> {
+            let (offset, count) = match self.get_entry(tag) {
+                Some(&(_, kind, offset, count)) if kind == TYPE_INT32 => (offset, count),
+                _ => return vec![],
+            };
+
+            (0..count)
+                .filter_map(|i| read_u32(self.data, offset + i * 4))
+                .collect()
+        }
+
+        pub fn get_name(&self) -> Option<&'a str> {
+            self.get_string(TAG_NAME)
+        }
+
+        pub fn get_version(&self) -> Option<String> {
+            let version = self.get_string(TAG_VERSION)?;
+
+            match self.get_string(TAG_RELEASE) {
+                Some(release) => Some(format!("{}-{}", version, release)),
+                None => Some(version.to_owned()),
+            }
+        }
+
+        pub fn get_paths(&self) -> Vec<std::path::PathBuf> {
+            let dirs = self.get_strings(TAG_DIRNAMES);
+            let names = self.get_strings(TAG_BASENAMES);
+            let indexes = self.get_int32s(TAG_DIRINDEXES);
+
+            names
+                .iter()
+                .zip(indexes)
+                .filter_map(|(name, index)| {
+                    let dir = dirs.get(index as usize)?;
+                    Some(Path::new(dir).join(name))
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "rpm")]
+fn add_rpm_owners(builder: &mut OwnersBuilder) {
+    use rusqlite::{Connection, OpenFlags};
+
+    let connection = match Connection::open_with_flags(
+        rooted(Path::new(RPM_SQLITE_DB)),
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    ) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let mut statement = match connection.prepare("SELECT blob FROM Packages") {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let blobs = match statement.query_map([], |row| row.get::<_, Vec<u8>>(0)) {
+        Ok(v) => v.flatten(),
+        Err(_) => return,
+    };
+
+    for blob in blobs {
+        let header = match rpm::RpmHeader::parse(&blob) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let package = match (header.get_name(), header.get_version()) {
+            (Some(name), Some(version)) => Arc::new(Package {
+                name: name.to_owned(),
+                version,
+                manager: "rpm",
+            }),
+            _ => continue,
+        };
+
+        let paths = header
+            .get_paths()
+            .into_iter()
+            .filter(|path| path.to_string_lossy().contains(BIN_DIR_MARKER));
+
+        for path in paths {
+            builder.insert(&rooted(&path), &package);
+        }
+    }
+}
+
+fn init_package_owners() -> PackageOwners {
+    let mut builder = OwnersBuilder::default();
+
+    add_dpkg_owners(&mut builder);
+    add_pacman_owners(&mut builder);
+
+    #[cfg(feature = "rpm")]
+    add_rpm_owners(&mut builder);
+
+    builder.owners
+}
+
+fn get_lookup_path(path: &Path) -> Option<PathBuf> {
+    let dir = canonicalize(path.parent()?).ok()?;
+    Some(dir.join(path.file_name()?))
+}
+
+pub fn get_package_owner(path: &Path) -> Option<Arc<Package>> {
+    let candidates = [
+        get_lookup_path(path),
+        canonicalize(path).ok(),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| PACKAGE_OWNERS.get(&candidate).cloned())
+}
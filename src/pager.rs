@@ -0,0 +1,92 @@
+use std::{
+    env,
+    io::{self, Read, Write},
+    process::{Command, Stdio},
+};
+
+use crate::{
+    app::is_tool_available,
+    binaries::Binary,
+    man::{ManpageBuffer, find_manpage, get_man_command},
+    roff::render_manpage,
+    terminal::tty_stdio,
+};
+
+const DEFAULT_PAGER: &str = "less";
+
+fn get_pager() -> String {
+    ["MANPAGER", "PAGER"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|pager| !pager.trim().is_empty())
+        .unwrap_or(DEFAULT_PAGER.to_owned())
+}
+
+fn get_not_found_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "no manual entry")
+}
+
+fn attach_tty(command: &mut Command, use_tty: bool) -> io::Result<()> {
+    if use_tty {
+        command
+            .stdin(tty_stdio()?)
+            .stdout(tty_stdio()?)
+            .stderr(tty_stdio()?);
+    }
+
+    Ok(())
+}
+
+fn open_with_man(binary: &Binary, use_tty: bool) -> io::Result<()> {
+    let mut man = get_man_command();
+
+    match &binary.manpath {
+        Some(path) => man.arg(path),
+        None => man.arg(&binary.name),
+    };
+
+    attach_tty(&mut man, use_tty)?;
+
+    match man.status()?.success() {
+        true => Ok(()),
+        false => Err(get_not_found_error()),
+    }
+}
+
+fn open_with_pager(binary: &Binary, use_tty: bool) -> io::Result<()> {
+    let path = binary.manpath.clone()
+        .or_else(|| find_manpage(&binary.name))
+        .ok_or_else(get_not_found_error)?;
+
+    let mut source = String::new();
+    ManpageBuffer::try_from(path.as_path())?.read_to_string(&mut source)?;
+
+    let mut pager = Command::new("sh");
+    pager
+        .arg("-c")
+        .arg(get_pager())
+        .stdin(Stdio::piped());
+
+    if use_tty {
+        pager
+            .stdout(tty_stdio()?)
+            .stderr(tty_stdio()?);
+    }
+
+    let mut child = pager.spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may quit before reading everything
+        let _ = stdin.write_all(render_manpage(&source).as_bytes());
+    }
+
+    child.wait()?;
+    Ok(())
+}
+
+pub fn open_manpage(binary: &Binary, use_tty: bool) -> io::Result<()> {
+    match is_tool_available("man") {
+        true => open_with_man(binary, use_tty),
+        false => open_with_pager(binary, use_tty),
+    }
+}
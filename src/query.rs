@@ -0,0 +1,395 @@
+use std::{path::PathBuf, sync::OnceLock};
+
+use clap::ValueEnum;
+use strsim::{jaro_winkler, osa_distance};
+
+use crate::{
+    binaries::Binary,
+    filetype::is_symlink,
+    man::get_man_sections,
+};
+
+const EXCLUDE_PREFIX: char = '-';
+const QUOTE: char = '"';
+const APP_NAME_FACTOR: f64 = 0.95;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Matcher {
+    Contains(String),
+    Dir(PathBuf),
+    HasMan,
+    Section(String),
+    Type(String),
+    Package(String),
+}
+
+impl Matcher {
+    fn from_field(field: &str, value: &str) -> Option<Self> {
+        let matcher = match field {
+            "dir" => Matcher::Dir(PathBuf::from(value)),
+            "has" if value == "man" => Matcher::HasMan,
+            "section" => Matcher::Section(value.to_owned()),
+            "type" => Matcher::Type(value.to_lowercase()),
+            "pkg" => Matcher::Package(value.to_owned()),
+            _ => return None,
+        };
+
+        Some(matcher)
+    }
+
+    fn is_match(&self, binary: &Binary) -> bool {
+        match self {
+            Matcher::Contains(term) => binary
+                .get_names()
+                .any(|name| name.contains(term.as_str())),
+            Matcher::Dir(dir) => binary.path.starts_with(dir),
+            Matcher::HasMan => get_man_sections(&binary.name).is_some(),
+            Matcher::Section(section) => get_man_sections(&binary.name)
+                .is_some_and(|sections| sections.contains(section)),
+            Matcher::Type(kind) => match kind.as_str() {
+                "symlink" | "link" => is_symlink(&binary.path),
+                marker if binary.kind.marker() == Some(marker) => true,
+                "multicall" => binary.multi_call.is_some(),
+                "offpath" => binary.off_path,
+                value if binary.multi_call.as_deref() == Some(value) => true,
+                value => binary
+                    .get_file_type()
+                    .is_some_and(|file_type| file_type.is_match(value)),
+            },
+            Matcher::Package(name) => binary
+                .get_package()
+                .is_some_and(|package| package.name == *name),
+        }
+    }
+}
+
+fn get_typo_similarity(a: &str, b: &str) -> f64 {
+    let length = a.chars().count().max(b.chars().count());
+
+    match length {
+        0 => 1.0,
+        _ => 1.0 - osa_distance(a, b) as f64 / length as f64,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    pub matcher: Matcher,
+    pub negated: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Scoring {
+    #[default]
+    Similarity,
+    Typo,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Query {
+    pub terms: Vec<String>,
+    pub clauses: Vec<Clause>,
+    pub scoring: Scoring,
+}
+
+struct Token {
+    value: String,
+    quoted: bool,
+    negated: bool,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let negated = chars.next_if_eq(&EXCLUDE_PREFIX).is_some();
+        let quoted = chars.next_if_eq(&QUOTE).is_some();
+
+        let mut value = String::new();
+
+        for c in chars.by_ref() {
+            match (quoted, c) {
+                (true, QUOTE) => break,
+                (false, c) if c.is_whitespace() => break,
+                _ => value.push(c),
+            }
+        }
+
+        if value.is_empty() {
+            continue;
+        }
+
+        tokens.push(Token { value, quoted, negated });
+    }
+
+    tokens
+}
+
+static DEFAULT_SCORING: OnceLock<Scoring> = OnceLock::new();
+
+pub fn init_default_scoring(scoring: Scoring) {
+    DEFAULT_SCORING.get_or_init(|| scoring);
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Query {
+            scoring: DEFAULT_SCORING.get().copied().unwrap_or_default(),
+            ..Default::default()
+        };
+
+        for token in tokenize(input) {
+            let Token { value, quoted, negated } = token;
+
+            let matcher = match value.split_once(':') {
+                Some((field, v)) if !quoted && !v.is_empty() => Matcher::from_field(field, v),
+                _ => None,
+            };
+
+            let matcher = match (matcher, quoted, negated) {
+                (Some(m), _, _) => m,
+                (None, false, false) => {
+                    query.terms.push(value);
+                    continue;
+                },
+                (None, true, false) => {
+                    query.terms.push(value.clone());
+                    Matcher::Contains(value)
+                },
+                (None, _, true) => Matcher::Contains(value),
+            };
+
+            query.clauses.push(Clause { matcher, negated });
+        }
+
+        query
+    }
+
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.clauses.is_empty()
+    }
+
+    pub fn matches(&self, binary: &Binary) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.matcher.is_match(binary) != clause.negated)
+    }
+
+    fn term_similarity(&self, name: &str) -> f64 {
+        let similarity = match self.scoring {
+            Scoring::Similarity => jaro_winkler,
+            Scoring::Typo => get_typo_similarity,
+        };
+
+        self.terms
+            .iter()
+            .map(|term| similarity(name, term))
+            .fold(0.0, f64::max)
+    }
+
+    pub fn similarity(&self, binary: &Binary) -> f64 {
+        if self.terms.is_empty() {
+            return 1.0;
+        }
+
+        if self.scoring == Scoring::Typo {
+            return binary
+                .get_names()
+                .map(|name| self.term_similarity(name))
+                .fold(0.0, f64::max);
+        }
+
+        let app_similarity = binary.apps
+            .iter()
+            .flat_map(|app| app.get_search_names())
+            .map(|name| {
+                let lowercase_similarity = self.terms
+                    .iter()
+                    .map(|term| jaro_winkler(&name, &term.to_lowercase()))
+                    .fold(0.0, f64::max);
+
+                lowercase_similarity * APP_NAME_FACTOR
+            })
+            .fold(0.0, f64::max);
+
+        binary
+            .get_names()
+            .map(|name| self.term_similarity(name))
+            .fold(app_similarity, f64::max)
+    }
+
+    pub fn get_highlights(&self, name: &str) -> Vec<bool> {
+        let name = name
+            .chars()
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<Vec<char>>();
+
+        let mut highlights = vec![false; name.len()];
+
+        let contains_terms = self.clauses
+            .iter()
+            .filter_map(|clause| match (&clause.matcher, clause.negated) {
+                (Matcher::Contains(term), false) => Some(term),
+                _ => None,
+            });
+
+        for term in self.terms.iter().chain(contains_terms) {
+            let term = term
+                .chars()
+                .map(|c| c.to_ascii_lowercase())
+                .collect::<Vec<char>>();
+
+            if term.is_empty() {
+                continue;
+            }
+
+            let start = name
+                .windows(term.len())
+                .position(|window| window == term.as_slice());
+
+            if let Some(start) = start {
+                highlights[start..start + term.len()].fill(true);
+                continue;
+            }
+
+            let mut position = 0;
+
+            for c in term {
+                match name[position..].iter().position(|v| *v == c) {
+                    Some(offset) => {
+                        highlights[position + offset] = true;
+                        position += offset + 1;
+                    },
+                    None => break,
+                }
+            }
+        }
+
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cmp::Reverse,
+        sync::{Arc, RwLock},
+    };
+
+    use ordered_float::OrderedFloat;
+
+    use super::*;
+    use crate::binaries::{BinSearchResult, BinaryKind, BinaryNode, search_binaries_cancellable};
+
+    fn binary(name: &str, dir: &str, kind: BinaryKind) -> Binary {
+        Binary::new(name.to_owned(), PathBuf::from(dir).join(name), kind)
+    }
+
+    fn contains(term: &str, negated: bool) -> Clause {
+        Clause { matcher: Matcher::Contains(term.to_owned()), negated }
+    }
+
+    #[test]
+    fn parse_splits_terms_and_clauses() {
+        let query = Query::parse(r#"  ls -foo "bar baz"  dir:/usr/bin type:ELF -pkg:coreutils "#);
+
+        assert_eq!(query.terms, ["ls", "bar baz"]);
+        assert_eq!(query.clauses, [
+            contains("foo", true),
+            contains("bar baz", false),
+            Clause { matcher: Matcher::Dir(PathBuf::from("/usr/bin")), negated: false },
+            Clause { matcher: Matcher::Type("elf".to_owned()), negated: false },
+            Clause { matcher: Matcher::Package("coreutils".to_owned()), negated: true },
+        ]);
+    }
+
+    #[test]
+    fn parse_keeps_unknown_and_quoted_fields_as_terms() {
+        let query = Query::parse(r#"foo:bar dir: "has:man" has:info"#);
+
+        assert_eq!(query.terms, ["foo:bar", "dir:", "has:man", "has:info"]);
+        assert_eq!(query.clauses, [contains("has:man", false)]);
+    }
+
+    #[test]
+    fn parse_skips_empty_tokens() {
+        let query = Query::parse(r#" - "" "#);
+
+        assert!(query.is_empty());
+    }
+
+    #[test]
+    fn clauses_match_names_dirs_and_kinds() {
+        let ls = binary("ls", "/usr/bin", BinaryKind::Executable);
+        let ll = binary("ll", "/", BinaryKind::Alias);
+
+        let query = Query::parse("-l");
+        assert!(!query.matches(&ls));
+
+        let query = Query::parse(r#""s" dir:/usr"#);
+        assert!(query.matches(&ls));
+        assert!(!query.matches(&ll));
+
+        let query = Query::parse("type:alias");
+        assert!(!query.matches(&ls));
+        assert!(query.matches(&ll));
+
+        let query = Query::parse("-type:alias -type:offpath");
+        assert!(query.matches(&ls));
+        assert!(!query.matches(&ll));
+    }
+
+    #[test]
+    fn clauses_match_aliases() {
+        let mut vim = binary("vim", "/usr/bin", BinaryKind::Executable);
+        vim.aliases.push("vi".to_owned());
+
+        assert!(Query::parse(r#""vi" -ex"#).matches(&vim));
+        assert!(!Query::parse("-vi").matches(&vim));
+    }
+
+    #[test]
+    fn equal_scores_are_ordered_by_name_then_kind() {
+        let binaries = [
+            binary("abz", "/usr/bin", BinaryKind::Executable),
+            binary("abx", "/usr/bin", BinaryKind::Alias),
+            binary("abx", "/usr/bin", BinaryKind::Executable),
+        ];
+
+        let mut base = BinSearchResult::default();
+
+        for (i, binary) in binaries.into_iter().enumerate() {
+            let key = (OrderedFloat(0.0), Reverse(i.to_string()), Reverse(binary.kind));
+            base.insert(key, Arc::new(RwLock::new(binary)) as BinaryNode);
+        }
+
+        let result = search_binaries_cancellable(&Query::parse("ab"), Some(&base), &|| false)
+            .unwrap();
+
+        let ordered = result
+            .ordered_iter()
+            .map(|binary| {
+                let readable_binary = binary.read().unwrap();
+                (readable_binary.name.clone(), readable_binary.kind)
+            })
+            .collect::<Vec<(String, BinaryKind)>>();
+
+        assert_eq!(ordered, [
+            ("abx".to_owned(), BinaryKind::Executable),
+            ("abx".to_owned(), BinaryKind::Alias),
+            ("abz".to_owned(), BinaryKind::Executable),
+        ]);
+    }
+}
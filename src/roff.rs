@@ -1,6 +1,7 @@
 use std::{
     io::{IoSlice, Write},
     process::{Command, Stdio},
+    thread,
 };
 
 use crate::{
@@ -158,3 +159,88 @@ pub fn extract_description_section(mut buf: ManpageBuffer) -> Option<Description
 
     Some((inner, section).into())
 }
+
+fn render_with_groff(source: &str) -> Option<String> {
+    let mut groff = Command::new("groff")
+        .args(["-Tutf8", "-mandoc", "-P-c"])
+        .stderr(Stdio::null())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let mut stdin = groff.stdin.take()?;
+    let source = source.to_owned();
+
+    // Written from another thread, since groff stops reading
+    // once its output pipe is full on long pages.
+    let writer = thread::spawn(move || stdin.write_all(source.as_bytes()));
+
+    let output = groff.wait_with_output().ok()?;
+    writer.join().ok()?.ok()?;
+
+    match output.status.success() {
+        true => String::from_utf8(output.stdout).ok(),
+        false => None,
+    }
+}
+
+fn strip_escapes(line: &str) -> String {
+    let mut text = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('f') => {
+                chars.next();
+            },
+            Some('-') => text.push('-'),
+            Some('e') => text.push('\\'),
+            Some('&') | None => {},
+            Some(c) => text.push(c),
+        }
+    }
+
+    text
+}
+
+// A plain text fallback for when groff is missing,
+// macros are dropped and their arguments kept as text.
+fn render_plain(source: &str) -> String {
+    let mut text = String::new();
+
+    for line in source.lines() {
+        if line.starts_with(".\\\"") || line.starts_with("'\\\"") {
+            continue;
+        }
+
+        let line = match line.strip_prefix('.') {
+            Some(macro_line) => match macro_line.split_once(char::is_whitespace) {
+                Some((name, args)) if name == "SH" || name == "Sh" => {
+                    text.push('\n');
+                    args.trim_matches('"').to_uppercase()
+                },
+                Some((_, args)) => args.replace('"', ""),
+                None => {
+                    text.push('\n');
+                    continue;
+                },
+            },
+            None => line.to_owned(),
+        };
+
+        text.push_str(&strip_escapes(&line));
+        text.push('\n');
+    }
+
+    text
+}
+
+pub fn render_manpage(source: &str) -> String {
+    render_with_groff(source).unwrap_or_else(|| render_plain(source))
+}
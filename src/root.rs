@@ -0,0 +1,109 @@
+use std::{
+    ffi::OsString,
+    fs,
+    io,
+    path::{Component, Path, PathBuf},
+    sync::OnceLock,
+};
+
+const MAX_SYMLINK_HOPS: usize = 40;
+
+static ROOT_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+pub fn init_root_dir(dir: Option<PathBuf>) {
+    ROOT_DIR.get_or_init(|| {
+        dir.map(|dir| fs::canonicalize(&dir).unwrap_or(dir))
+    });
+}
+
+pub fn get_root_dir() -> Option<&'static Path> {
+    ROOT_DIR
+        .get()
+        .and_then(|dir| dir.as_deref())
+}
+
+pub fn rooted(path: &Path) -> PathBuf {
+    let root = match get_root_dir() {
+        Some(v) => v,
+        None => return path.to_path_buf(),
+    };
+
+    if path.starts_with(root) {
+        return path.to_path_buf();
+    }
+
+    let relative = path
+        .strip_prefix("/")
+        .unwrap_or(path);
+
+    root.join(relative)
+}
+
+fn push_components(pending: &mut Vec<OsString>, path: &Path) {
+    let components = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        });
+
+    let start = pending.len();
+    pending.extend(components);
+    pending[start..].reverse();
+}
+
+pub fn canonicalize(path: &Path) -> io::Result<PathBuf> {
+    let root = match get_root_dir() {
+        Some(v) => v,
+        None => return fs::canonicalize(path),
+    };
+
+    let relative = path
+        .strip_prefix(root)
+        .unwrap_or(path);
+
+    let mut pending = Vec::new();
+    let mut resolved = PathBuf::new();
+    let mut hops = 0;
+
+    push_components(&mut pending, relative);
+
+    while let Some(name) = pending.pop() {
+        if name == ".." {
+            resolved.pop();
+            continue;
+        }
+
+        let candidate = root.join(&resolved).join(&name);
+        let metadata = fs::symlink_metadata(&candidate)?;
+
+        if !metadata.is_symlink() {
+            resolved.push(name);
+            continue;
+        }
+
+        hops += 1;
+
+        if hops > MAX_SYMLINK_HOPS {
+            return Err(io::Error::other("too many levels of symbolic links"));
+        }
+
+        let target = fs::read_link(&candidate)?;
+
+        if target.is_absolute() {
+            resolved.clear();
+        }
+
+        push_components(&mut pending, &target);
+    }
+
+    Ok(root.join(resolved))
+}
+
+pub fn metadata(path: &Path) -> io::Result<fs::Metadata> {
+    match get_root_dir() {
+        Some(_) => fs::metadata(canonicalize(path)?),
+        None => fs::metadata(path),
+    }
+}
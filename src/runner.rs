@@ -0,0 +1,75 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::process::ExitStatusExt,
+    process::{Command, ExitStatus},
+};
+
+use crate::{
+    binaries::{Binary, BinaryKind},
+    terminal::{open_tty, tty_stdio},
+};
+
+const DEFAULT_SHELL: &str = "sh";
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// Aliases, functions and builtins only exist inside the user's shell,
+// so they go through an interactive one.
+fn get_run_command(binary: &Binary, args: &str) -> Command {
+    let (shell, flag, target) = match binary.kind {
+        BinaryKind::Executable => (
+            DEFAULT_SHELL.to_owned(),
+            "-c",
+            quote(&binary.path.to_string_lossy()),
+        ),
+        _ => (
+            env::var("SHELL").unwrap_or(DEFAULT_SHELL.to_owned()),
+            "-ic",
+            binary.name.clone(),
+        ),
+    };
+
+    let mut command = Command::new(shell);
+    command
+        .arg(flag)
+        .arg(format!("{} {}", target, args.trim()));
+
+    command
+}
+
+pub fn get_status_text(name: &str, status: &ExitStatus) -> String {
+    match (status.code(), status.signal()) {
+        (Some(code), _) => format!("{} exited with {}", name, code),
+        (None, Some(signal)) => format!("{} killed by signal {}", name, signal),
+        _ => format!("{} exited", name),
+    }
+}
+
+fn wait_for_enter(status_text: &str) -> io::Result<()> {
+    let mut tty = open_tty()?;
+
+    write!(tty, "\n[{}] press Enter to return", status_text)?;
+    tty.flush()?;
+
+    BufReader::new(tty).read_line(&mut String::new())?;
+    Ok(())
+}
+
+pub fn run_binary(binary: &Binary, args: &str, use_tty: bool) -> io::Result<String> {
+    let mut command = get_run_command(binary, args);
+
+    if use_tty {
+        command
+            .stdin(tty_stdio()?)
+            .stdout(tty_stdio()?)
+            .stderr(tty_stdio()?);
+    }
+
+    let status_text = get_status_text(&binary.name, &command.status()?);
+    let _ = wait_for_enter(&status_text);
+
+    Ok(status_text)
+}
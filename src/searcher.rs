@@ -0,0 +1,95 @@
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    binaries::{BinSearchResult, search_binaries_cancellable},
+    query::Query,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(60);
+
+pub struct SearchOutput {
+    pub value: String,
+    pub binaries: BinSearchResult,
+    pub elapsed: Duration,
+}
+
+pub struct Searcher {
+    generation: Arc<AtomicU64>,
+    output: Arc<Mutex<Option<SearchOutput>>>,
+    redraw: Arc<AtomicBool>,
+}
+
+impl Searcher {
+    pub fn new(redraw: Arc<AtomicBool>) -> Self {
+        Self {
+            generation: Arc::new(AtomicU64::new(0)),
+            output: Arc::new(Mutex::new(None)),
+            redraw,
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.output.lock().unwrap().take();
+    }
+
+    pub fn request(&self, value: String, base: Option<BinSearchResult>) {
+        self.cancel();
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let current_generation = self.generation.clone();
+        let output = self.output.clone();
+        let redraw_req = self.redraw.clone();
+
+        thread::spawn(move || {
+            let is_cancelled = || {
+                current_generation.load(Ordering::Acquire) != generation
+            };
+
+            thread::sleep(DEBOUNCE);
+
+            if is_cancelled() {
+                return;
+            }
+
+            let query = Query::parse(&value);
+            let start = Instant::now();
+
+            let result = search_binaries_cancellable(
+                &query,
+                base.as_ref(),
+                &is_cancelled,
+            );
+
+            let binaries = match result {
+                Some(v) => v,
+                None => return,
+            };
+
+            let mut output = output.lock().unwrap();
+
+            if is_cancelled() {
+                return;
+            }
+
+            *output = Some(SearchOutput {
+                value,
+                binaries,
+                elapsed: start.elapsed(),
+            });
+            redraw_req.store(true, Ordering::Release);
+        });
+    }
+
+    pub fn take_result(&self) -> Option<SearchOutput> {
+        self.output.lock().unwrap().take()
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    sync::OnceLock,
+};
+
+use crate::{
+    binaries::{Binary, BinaryKind},
+    xdg::sb_state_file,
+};
+
+const SHELL_ENTRIES_FILE: &str = "shell";
+const STDIN_SOURCE: &str = "-";
+
+static SHELL_ENTRIES_SOURCE: OnceLock<String> = OnceLock::new();
+
+pub fn init_shell_entries_source(source: &str) {
+    SHELL_ENTRIES_SOURCE.get_or_init(|| source.to_owned());
+}
+
+fn read_source(source: &str) -> Option<String> {
+    let mut content = String::new();
+
+    match source {
+        STDIN_SOURCE => {
+            io::stdin().read_to_string(&mut content).ok()?;
+        },
+        "" => {
+            let path = sb_state_file(SHELL_ENTRIES_FILE)?;
+            content = fs::read_to_string(path).ok()?;
+        },
+        path => {
+            content = fs::read_to_string(path).ok()?;
+        },
+    }
+
+    Some(content)
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+
+    for quote in ['\'', '"'] {
+        if value.len() >= 2
+        && value.starts_with(quote)
+        && value.ends_with(quote)
+        {
+            return value[1..value.len() - 1].replace("'\\''", "'");
+        }
+    }
+
+    value.to_owned()
+}
+
+fn is_name(value: &str) -> bool {
+    !value.is_empty()
+    && !value.contains(char::is_whitespace)
+    && !value.contains(['=', '(', ')', '{', '}'])
+}
+
+fn parse_alias(line: &str) -> Option<(String, String)> {
+    let stripped = line.strip_prefix("alias ");
+    let line = stripped.unwrap_or(line);
+
+    if let Some((name, value)) = line.split_once('=')
+    && is_name(name)
+    {
+        return Some((name.to_owned(), unquote(value)));
+    }
+
+    stripped?;
+
+    let (name, value) = line.split_once(' ')?;
+
+    is_name(name).then(|| (name.to_owned(), unquote(value)))
+}
+
+fn parse_function_header(line: &str) -> Option<&str> {
+    let line = line.trim_end();
+    let line = line.strip_suffix('{').unwrap_or(line).trim_end();
+
+    let name = match line.strip_prefix("function ") {
+        Some(v) => v.trim().trim_end_matches("()").trim_end(),
+        None => line.strip_suffix("()")?.trim_end(),
+    };
+
+    is_name(name).then_some(name)
+}
+
+fn new_entry(name: &str, kind: BinaryKind, definition: Option<String>) -> Binary {
+    let mut entry = Binary::new(name.to_owned(), PathBuf::new(), kind);
+    entry.definition = definition;
+    entry
+}
+
+fn parse_shell_entries(content: &str) -> Vec<Binary> {
+    let mut entries = Vec::new();
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let words = trimmed
+            .split_whitespace()
+            .collect::<Vec<&str>>();
+
+        match words.as_slice() {
+            ["declare", flags, name] if flags.starts_with("-f") => {
+                entries.push(new_entry(name, BinaryKind::Function, None));
+                continue;
+            },
+            ["enable" | "builtin", name] => {
+                entries.push(new_entry(name, BinaryKind::Builtin, None));
+                continue;
+            },
+            _ => {},
+        }
+
+        if let Some(name) = parse_function_header(trimmed) {
+            let mut body = Vec::new();
+
+            while let Some(body_line) = lines.next_if(|l| !l.starts_with('}')) {
+                let body_line = body_line.trim();
+
+                if !body_line.is_empty() && body_line != "{" {
+                    body.push(body_line);
+                }
+            }
+
+            lines.next();
+
+            let definition = Some(body.join("; "))
+                .filter(|v| !v.is_empty());
+
+            entries.push(new_entry(name, BinaryKind::Function, definition));
+            continue;
+        }
+
+        if let Some((name, value)) = parse_alias(trimmed) {
+            entries.push(new_entry(&name, BinaryKind::Alias, Some(value)));
+        }
+    }
+
+    entries
+}
+
+pub fn get_shell_entries() -> Vec<Binary> {
+    let content = SHELL_ENTRIES_SOURCE
+        .get()
+        .and_then(|source| read_source(source));
+
+    match content {
+        Some(v) => parse_shell_entries(&v),
+        None => vec![],
+    }
+}
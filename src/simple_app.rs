@@ -1,14 +1,16 @@
+use std::collections::BTreeSet;
+
 use color_eyre::eyre::Result as RepResult;
+use serde_json::Value;
 
 use crate::{
     app::App,
-    binaries::{
-        BinaryNode,
-        attach_manpaths,
-        search_binaries,
-    },
+    binaries::OFF_PATH_MARKER,
     cli::Cli,
     clipboard::clipboard_copy,
+    daemon::{SearchRequest, SearchSettings, query_daemon, run_search_request},
+    frecency::record_pick,
+    history::record_history,
 };
 
 #[derive(Default)]
@@ -22,48 +24,94 @@ impl App for SimpleApp {
     }
 
     fn run(&mut self) -> RepResult<()> {
-        let input = self.args.input.as_ref().unwrap();
+        let request = SearchRequest {
+            query: self.args.input.clone().unwrap(),
+            limit: self.args.result_length,
+            descriptions: self.args.show_descriptions,
+            details: self.args.json_output,
+            settings: Some(SearchSettings::from_args(&self.args)),
+        };
+
+        let response = match self.can_use_daemon() {
+            true => query_daemon(&request),
+            false => None,
+        };
+
+        let response = response
+            .unwrap_or_else(|| run_search_request(&request));
 
-        let search_result = search_binaries(input);
-        let mut result_iter = search_result
-            .owned_ordered_iter()
-            .peekable();
+        let results = match response.get("results") {
+            Some(Value::Array(v)) => v.as_slice(),
+            _ => &[],
+        };
 
-        if let Some(b) = result_iter.peek()
+        let mut accepted = vec![];
+
+        if let Some(name) = results.first().and_then(|v| v["name"].as_str())
            && self.args.should_copy_result
         {
-            let readable_binary = b.read().unwrap();
-            let name = &readable_binary.name;
-
             let _ = clipboard_copy(name);
-            drop(readable_binary);
+            record_pick(name);
+            accepted.push(name.to_owned());
         }
 
-        let binaries = result_iter
-            .take(self.args.result_length)
-            .collect::<Vec<BinaryNode>>();
+        record_history(&request.query, &accepted);
 
-        if self.args.show_descriptions {
-            attach_manpaths(&binaries);
+        if self.args.json_output {
+            println!("{}", serde_json::to_string_pretty(results)?);
+            return Ok(());
         }
 
-        for binary in binaries {
-            let readable_binary = binary.read().unwrap();
+        self.print_results(results);
 
-            print!("{}", readable_binary.name);
+        Ok(())
+    }
+}
+
+impl SimpleApp {
+    // Shell entries from stdin only exist in this process.
+    fn can_use_daemon(&self) -> bool {
+        self.args.shell_entries.as_deref() != Some("-")
+    }
+
+    fn get_marker(result: &Value) -> Option<&str> {
+        match result["kind"].as_str() {
+            Some("executable") | None => result["off_path"]
+                .as_bool()
+                .unwrap_or(false)
+                .then_some(OFF_PATH_MARKER),
+            kind => kind,
+        }
+    }
+
+    fn print_results(&self, results: &[Value]) {
+        let mut hints = BTreeSet::new();
+
+        for result in results {
+            print!("{}", result["name"].as_str().unwrap_or_default());
+
+            if let Some(hint) = result["hint"].as_str() {
+                hints.insert(hint);
+            }
+
+            if let Some(marker) = Self::get_marker(result) {
+                print!(" [{}]", marker);
+            }
 
             if !self.args.show_descriptions {
-                print!("\n");
+                println!();
                 continue;
             }
 
-            if let Some(desc) = readable_binary.get_description() {
-                println!(":\n{}", desc.value);
+            if let Some(desc) = result["description"].as_str() {
+                println!(":\n{}", desc);
             } else {
                 println!(",");
             }
         }
 
-        Ok(())
+        for hint in hints {
+            eprintln!("off-PATH binaries are reachable with: {}", hint);
+        }
     }
 }
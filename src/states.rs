@@ -1,15 +1,23 @@
-#[cfg(debug_assertions)]
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
-use ratatui::layout::Position;
+use ratatui::layout::{Position, Rect};
 
-use crate::binaries::BinSearchResult;
+use crate::binaries::{BinSearchResult, BinaryNode};
 
 #[derive(Default)]
 pub struct CursorState {
     pub position: Option<Position>,
 }
 
+#[derive(Default)]
+pub struct MouseState {
+    pub input_area: Rect,
+    pub result_area: Rect,
+    pub details_area: Option<Rect>,
+    pub item_areas: Vec<Rect>,
+    pub last_click: Option<(Instant, Position, BinaryNode)>,
+}
+
 #[cfg(debug_assertions)]
 pub struct TickState {
     pub count: u32,
@@ -31,4 +39,48 @@ impl Default for TickState {
 pub struct BinaryListState {
     pub binaries: BinSearchResult,
     pub selected: usize,
+    pub marked: Vec<BinaryNode>,
+}
+
+impl BinaryListState {
+    pub fn get_selected(&self) -> Option<&BinaryNode> {
+        self.binaries
+            .ordered_iter()
+            .nth(self.selected)
+    }
+
+    pub fn is_marked(&self, binary: &BinaryNode) -> bool {
+        self.marked
+            .iter()
+            .any(|marked| Arc::ptr_eq(marked, binary))
+    }
+
+    pub fn toggle_mark(&mut self) {
+        let selected = match self.get_selected() {
+            Some(v) => v.clone(),
+            None => return,
+        };
+
+        match self.marked.iter().position(|marked| Arc::ptr_eq(marked, &selected)) {
+            Some(i) => {
+                self.marked.remove(i);
+            },
+            None => self.marked.push(selected),
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    pub fn get_targets(&self) -> Vec<BinaryNode> {
+        if !self.marked.is_empty() {
+            return self.marked.clone();
+        }
+
+        self.get_selected()
+            .cloned()
+            .into_iter()
+            .collect()
+    }
 }
@@ -0,0 +1,243 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    os::fd::AsRawFd,
+    panic,
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result as RepResult;
+
+use ratatui::{
+    DefaultTerminal, Terminal, TerminalOptions, Viewport,
+    backend::{Backend, CrosstermBackend},
+    crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture},
+        execute,
+        terminal::{
+            self,
+            Clear,
+            ClearType,
+            EnterAlternateScreen,
+            LeaveAlternateScreen,
+            ScrollUp,
+            disable_raw_mode,
+            enable_raw_mode,
+        },
+    },
+    layout::{Position, Rect},
+};
+
+use crate::cli::Height;
+
+const TTY_PATH: &str = "/dev/tty";
+const MIN_INLINE_HEIGHT: u16 = 6;
+const CURSOR_QUERY: &[u8] = b"\x1b[6n";
+const CURSOR_QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+
+pub type TtyTerminal = Terminal<CrosstermBackend<File>>;
+
+pub fn open_tty() -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TTY_PATH)
+}
+
+pub fn tty_stdio() -> io::Result<Stdio> {
+    open_tty().map(Stdio::from)
+}
+
+fn get_inline_height(height: Height) -> io::Result<u16> {
+    let (_, rows) = terminal::size()?;
+
+    let lines = match height {
+        Height::Lines(n) => n,
+        Height::Percent(p) => (rows as u32 * p as u32 / 100) as u16,
+    };
+
+    Ok(lines.clamp(MIN_INLINE_HEIGHT.min(rows), rows))
+}
+
+fn parse_cursor_position(response: &[u8]) -> Option<Position> {
+    let response = String::from_utf8_lossy(response);
+
+    let (row, column) = response
+        .rsplit("\x1b[")
+        .next()?
+        .strip_suffix('R')?
+        .split_once(';')?;
+
+    Some(Position::new(
+        column.parse::<u16>().ok()?.saturating_sub(1),
+        row.parse::<u16>().ok()?.saturating_sub(1),
+    ))
+}
+
+// Reads are bounded by poll(2), so a terminal that never answers
+// leaves nothing behind to swallow later keystrokes.
+fn get_tty_cursor_position(tty: &mut File) -> Option<Position> {
+    use std::io::Read;
+
+    tty.write_all(CURSOR_QUERY).ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + CURSOR_QUERY_TIMEOUT;
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+
+    while response.last() != Some(&b'R') {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+
+        let mut poll_fd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        // Safety: poll_fd is a single valid pollfd for the duration of the call.
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) };
+
+        if ready <= 0 || tty.read(&mut byte).ok()? != 1 {
+            return None;
+        }
+
+        response.push(byte[0]);
+    }
+
+    parse_cursor_position(&response)
+}
+
+fn reserve_tty_area(tty: &mut File, height: u16) -> io::Result<(Rect, Position)> {
+    let (columns, rows) = terminal::size()?;
+
+    let cursor = get_tty_cursor_position(tty)
+        .unwrap_or(Position::new(0, rows.saturating_sub(1)));
+
+    let top = match cursor.x {
+        0 => cursor.y,
+        _ => cursor.y + 1,
+    };
+
+    let overflow = (top + height).saturating_sub(rows);
+
+    if overflow > 0 {
+        execute!(tty, ScrollUp(overflow))?;
+    }
+
+    let area = Rect::new(0, top - overflow, columns, height);
+    let origin = Position::new(cursor.x, cursor.y.saturating_sub(overflow));
+
+    Ok((area, origin))
+}
+
+pub fn init_stdout_terminal(height: Option<Height>) -> RepResult<DefaultTerminal> {
+    let terminal = match height {
+        Some(v) => {
+            let options = TerminalOptions {
+                viewport: Viewport::Inline(get_inline_height(v)?),
+            };
+
+            ratatui::try_init_with_options(options)?
+        },
+        None => ratatui::init(),
+    };
+
+    execute!(io::stdout(), EnableMouseCapture)?;
+
+    Ok(terminal)
+}
+
+fn set_tty_panic_hook(is_inline: bool) {
+    let hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal(true, is_inline);
+        hook(info);
+    }));
+}
+
+pub fn init_tty_terminal(height: Option<Height>) -> RepResult<(TtyTerminal, Option<Position>)> {
+    let mut tty = open_tty()?;
+
+    enable_raw_mode()?;
+    // ratatui only hooks panics for the terminals it creates on stdout.
+    set_tty_panic_hook(height.is_some());
+
+    let (viewport, origin) = match height {
+        Some(v) => {
+            let (area, origin) = reserve_tty_area(&mut tty, get_inline_height(v)?)?;
+            (Viewport::Fixed(area), Some(origin))
+        },
+        None => {
+            execute!(tty, EnterAlternateScreen)?;
+            (Viewport::Fullscreen, None)
+        },
+    };
+
+    execute!(tty, EnableMouseCapture)?;
+
+    let options = TerminalOptions { viewport };
+    let terminal = Terminal::with_options(CrosstermBackend::new(tty), options)?;
+
+    Ok((terminal, origin))
+}
+
+pub fn clear_inline<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
+    origin: Option<Position>,
+) -> io::Result<()> {
+    let area = terminal.get_frame().area();
+
+    terminal.set_cursor_position(area.as_position())?;
+    execute!(terminal.backend_mut(), Clear(ClearType::FromCursorDown))?;
+
+    if let Some(origin) = origin {
+        terminal.set_cursor_position(origin)?;
+    }
+
+    terminal.show_cursor()
+}
+
+fn enter_screen<W: Write>(writer: &mut W, is_inline: bool) -> io::Result<()> {
+    if !is_inline {
+        execute!(writer, EnterAlternateScreen)?;
+    }
+
+    execute!(writer, EnableMouseCapture)
+}
+
+fn leave_screen<W: Write>(writer: &mut W, is_inline: bool) -> io::Result<()> {
+    execute!(writer, DisableMouseCapture)?;
+
+    if !is_inline {
+        execute!(writer, LeaveAlternateScreen)?;
+    }
+
+    Ok(())
+}
+
+pub fn restore_terminal(use_tty: bool, is_inline: bool) {
+    let _ = disable_raw_mode();
+
+    let _ = match use_tty {
+        true => open_tty().and_then(|mut tty| leave_screen(&mut tty, is_inline)),
+        false => leave_screen(&mut io::stdout(), is_inline),
+    };
+}
+
+pub fn resume_terminal<B: Backend>(
+    terminal: &mut Terminal<B>,
+    use_tty: bool,
+    is_inline: bool,
+) -> io::Result<()> {
+    enable_raw_mode()?;
+
+    match use_tty {
+        true => enter_screen(&mut open_tty()?, is_inline)?,
+        false => enter_screen(&mut io::stdout(), is_inline)?,
+    }
+
+    terminal.clear()
+}
@@ -0,0 +1,153 @@
+use std::{env, str::FromStr, sync::OnceLock};
+
+use ratatui::style::{Color, Modifier, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeName {
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            "high-contrast" => Some(Self::HighContrast),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub selection: Style,
+    pub name: Style,
+    pub highlight: Style,
+    pub app_name: Style,
+    pub description: Style,
+    pub secondary: Style,
+    pub border: Style,
+    pub status: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        match is_color_disabled() {
+            true => Self::no_color(),
+            false => Self::from_name(ThemeName::Dark),
+        }
+    }
+}
+
+impl Theme {
+    pub fn from_name(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::HighContrast => Self::high_contrast(),
+        }
+    }
+
+    fn dark() -> Self {
+        Self {
+            selection: Style::new().bg(Color::Cyan),
+            name: Style::new()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            highlight: Style::new().fg(Color::LightYellow),
+            app_name: Style::new().fg(Color::Gray),
+            description: Style::new(),
+            secondary: Style::new().fg(Color::DarkGray),
+            border: Style::new(),
+            status: Style::new().fg(Color::Gray),
+        }
+    }
+
+    fn light() -> Self {
+        Self {
+            selection: Style::new().bg(Color::LightCyan),
+            name: Style::new()
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            highlight: Style::new().fg(Color::Blue),
+            app_name: Style::new().fg(Color::DarkGray),
+            description: Style::new().fg(Color::Black),
+            secondary: Style::new().fg(Color::DarkGray),
+            border: Style::new().fg(Color::DarkGray),
+            status: Style::new().fg(Color::DarkGray),
+        }
+    }
+
+    fn high_contrast() -> Self {
+        Self {
+            selection: Style::new()
+                .fg(Color::Black)
+                .bg(Color::White),
+            name: Style::new()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            highlight: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            app_name: Style::new().fg(Color::White),
+            description: Style::new().fg(Color::White),
+            secondary: Style::new().fg(Color::White),
+            border: Style::new().fg(Color::White),
+            status: Style::new()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    fn no_color() -> Self {
+        Self {
+            selection: Style::new().add_modifier(Modifier::REVERSED),
+            name: Style::new().add_modifier(Modifier::BOLD),
+            highlight: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            app_name: Style::new(),
+            description: Style::new(),
+            secondary: Style::new(),
+            border: Style::new(),
+            status: Style::new(),
+        }
+    }
+
+    pub fn set_color(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let color = Color::from_str(value)
+            .map_err(|_| format!("invalid color: {}", value))?;
+
+        let style = match key {
+            "selection" => {
+                self.selection = self.selection.bg(color);
+                return Ok(());
+            },
+            "name" => &mut self.name,
+            "highlight" => &mut self.highlight,
+            "app_name" => &mut self.app_name,
+            "description" => &mut self.description,
+            "secondary" => &mut self.secondary,
+            "border" => &mut self.border,
+            "status" => &mut self.status,
+            _ => return Err(format!("unknown color: {}", key)),
+        };
+
+        *style = style.fg(color);
+        Ok(())
+    }
+}
+
+fn is_color_disabled() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+pub fn init_theme(theme: Theme) {
+    THEME.get_or_init(|| theme);
+}
+
+pub fn get_theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
@@ -0,0 +1,119 @@
+use std::{
+    env,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::xdg::{data_home, home_dir};
+
+struct ToolchainRoot {
+    dir: PathBuf,
+    env_file: Option<PathBuf>,
+}
+
+static TOOLCHAINS_ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub fn init_toolchains(enabled: bool) {
+    TOOLCHAINS_ENABLED.get_or_init(|| enabled);
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+fn get_pipx_venv_dirs(home: &Path) -> Vec<PathBuf> {
+    let venv_roots = [
+        data_home().map(|dir| dir.join("pipx/venvs")),
+        Some(home.join(".local/pipx/venvs")),
+    ];
+
+    venv_roots
+        .into_iter()
+        .flatten()
+        .filter_map(|root| fs::read_dir(root).ok())
+        .flatten()
+        .flatten()
+        .map(|venv| venv.path().join("bin"))
+        .collect()
+}
+
+fn get_toolchain_roots() -> Vec<ToolchainRoot> {
+    let home = match home_dir() {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let cargo_home = env_dir("CARGO_HOME").unwrap_or(home.join(".cargo"));
+    let go_path = env_dir("GOPATH").unwrap_or(home.join("go"));
+    let nix_profile = home.join(".nix-profile");
+
+    let mut roots = vec![
+        ToolchainRoot {
+            dir: cargo_home.join("bin"),
+            env_file: Some(cargo_home.join("env")),
+        },
+        ToolchainRoot {
+            dir: env_dir("GOBIN").unwrap_or(go_path.join("bin")),
+            env_file: None,
+        },
+        ToolchainRoot {
+            dir: home.join(".local/bin"),
+            env_file: None,
+        },
+        ToolchainRoot {
+            dir: home.join(".npm-global/bin"),
+            env_file: None,
+        },
+        ToolchainRoot {
+            dir: home.join(".bun/bin"),
+            env_file: None,
+        },
+        ToolchainRoot {
+            dir: home.join(".deno/bin"),
+            env_file: None,
+        },
+        ToolchainRoot {
+            dir: nix_profile.join("bin"),
+            env_file: Some(nix_profile.join("etc/profile.d/nix.sh")),
+        },
+    ];
+
+    let pipx_roots = get_pipx_venv_dirs(&home)
+        .into_iter()
+        .map(|dir| ToolchainRoot { dir, env_file: None });
+
+    roots.extend(pipx_roots);
+    roots
+}
+
+pub fn get_toolchain_dirs(search_paths: &[PathBuf]) -> Vec<PathBuf> {
+    if !TOOLCHAINS_ENABLED.get().copied().unwrap_or(false) {
+        return vec![];
+    }
+
+    get_toolchain_roots()
+        .into_iter()
+        .map(|root| root.dir)
+        .filter(|dir| dir.is_dir() && !search_paths.contains(dir))
+        .collect()
+}
+
+pub fn get_path_hint(path: &Path) -> Option<String> {
+    let dir = path.parent()?;
+
+    let env_file = get_toolchain_roots()
+        .into_iter()
+        .find(|root| root.dir == dir)
+        .and_then(|root| root.env_file)
+        .filter(|file| file.is_file());
+
+    let hint = match env_file {
+        Some(file) => format!(". \"{}\"", file.display()),
+        None => format!("export PATH=\"{}:$PATH\"", dir.display()),
+    };
+
+    Some(hint)
+}
@@ -1,12 +1,12 @@
-use std::sync::Arc;
+use std::{iter, sync::Arc, time::Duration};
 
+use clap::ValueEnum;
 use ratatui::{
     buffer::Buffer,
     layout::{Position, Rect},
     text::Span,
     widgets::{Block, BorderType, Padding, Widget},
-    crossterm::style::Color,
-    style::{Modifier, Style, Stylize},
+    style::Style,
     text::{Line, Text},
     widgets::{Borders, Paragraph, StatefulWidget, Wrap},
 };
@@ -14,18 +14,24 @@ use ratatui::{
 use tui_input::Input;
 
 use crate::{
+    binaries::Binary,
     descriptions::Description,
-    states::{BinaryListState, CursorState}
+    keymap::InputMode,
+    states::{BinaryListState, CursorState},
+    query::{Query, Scoring},
+    theme::{Theme, get_theme},
 };
 
 pub struct SearchInput<'inner, 'cursor> {
     pub inner: &'inner Input,
+    pub title: Option<&'inner str>,
+    pub mode: Option<InputMode>,
     pub cursor_state: &'cursor mut CursorState,
 }
 
 impl<'a, 'b> SearchInput<'a, 'b> {
     fn get_cursor_position(input: &Input, area: Rect) -> Position {
-        let width = area.width.max(3) - 4;
+        let width = area.width.saturating_sub(4);
         let scroll = input.visual_scroll(width as usize);
 
         let x = input.visual_cursor().max(scroll) - scroll + 2;
@@ -39,10 +45,24 @@ impl<'a, 'b> Widget for SearchInput<'a, 'b> {
         let text = self.inner.value();
         let text_span = Span::raw(text);
 
-        let outer_block = Block::bordered()
+        let mut outer_block = Block::bordered()
             .border_type(BorderType::Rounded)
+            .border_style(get_theme().border)
             .padding(Padding::left(1));
 
+        if let Some(title) = self.title {
+            outer_block = outer_block.title(Line::styled(format!(" {} ", title), get_theme().status));
+        }
+
+        if let Some(mode) = self.mode {
+            let mode_text = match mode {
+                InputMode::Insert => " INSERT ",
+                InputMode::Normal => " NORMAL ",
+            };
+
+            outer_block = outer_block.title(Line::styled(mode_text, get_theme().status).right_aligned());
+        }
+
         let inner_area = outer_block.inner(area);
 
         outer_block.render(area, buf);
@@ -53,8 +73,15 @@ impl<'a, 'b> Widget for SearchInput<'a, 'b> {
     }
 }
 
+const MARK_PREFIX: &str = "+ ";
+
 pub struct SearchResultItem<'bin> {
     pub name: &'bin String,
+    pub highlights: Vec<bool>,
+    pub marked: bool,
+    pub marker: Option<&'static str>,
+    pub app_name: Option<&'bin str>,
+    pub aliases: &'bin [String],
     pub description: Option<Arc<Description>>,
 }
 
@@ -66,6 +93,40 @@ pub enum SearchResultItemOrder {
 }
 
 impl<'a> SearchResultItem<'a> {
+    fn get_title(&self, theme: &Theme) -> Line<'a> {
+        let mut runs: Vec<(bool, String)> = Vec::new();
+        let mut spans = Vec::new();
+
+        if self.marked {
+            spans.push(Span::styled(MARK_PREFIX, theme.highlight));
+        }
+
+        let highlights = self.highlights
+            .iter()
+            .copied()
+            .chain(iter::repeat(false));
+
+        for (c, is_highlighted) in self.name.chars().zip(highlights) {
+            match runs.last_mut() {
+                Some((v, run)) if *v == is_highlighted => run.push(c),
+                _ => runs.push((is_highlighted, c.to_string())),
+            }
+        }
+
+        spans.extend(runs
+            .into_iter()
+            .map(|(is_highlighted, run)| {
+                let style = match is_highlighted {
+                    true => theme.name.patch(theme.highlight),
+                    false => theme.name,
+                };
+
+                Span::styled(run, style)
+            }));
+
+        Line::from(spans)
+    }
+
     pub fn calculate_height(&self, area: &Rect) -> u16 {
         let calculate_desc_height = |desc: &Description| {
             let mut height = 0;
@@ -94,9 +155,11 @@ impl<'a> StatefulWidget for SearchResultItem<'a> {
     type State = SearchResultItemOrder;
 
     fn render(self, area: Rect, buf: &mut Buffer, order: &mut Self::State) {
-        let bg = match order {
-            Self::State::Selected => Color::DarkCyan,
-            _ => Color::Reset,
+        let theme = get_theme();
+
+        let item_style = match order {
+            Self::State::Selected => theme.selection,
+            _ => Style::new(),
         };
 
         let borders = match order {
@@ -107,23 +170,40 @@ impl<'a> StatefulWidget for SearchResultItem<'a> {
         let block = Block::new()
             .borders(borders)
             .padding(Padding::horizontal(1))
-            .border_style(Style::new().dark_gray());
+            .border_style(theme.secondary);
 
-        let title = Line::styled(
-            self.name,
-            Style::default()
-                .white()
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        );
+        let mut title = self.get_title(theme);
+
+        if let Some(app_name) = self.app_name {
+            title.push_span(Span::styled(
+                format!(" ({})", app_name),
+                theme.app_name,
+            ));
+        }
+
+        if !self.aliases.is_empty() {
+            title.push_span(Span::styled(
+                format!(" aka {}", self.aliases.join(", ")),
+                theme.secondary,
+            ));
+        }
+
+        if let Some(marker) = self.marker {
+            title.push_span(Span::styled(
+                format!(" [{}]", marker),
+                theme.secondary,
+            ));
+        }
 
         let mut text = Text::from(title);
 
         if let Some(desc) = &self.description {
-            let description = Text::from("\n".to_owned() + desc.value.as_str());
+            let description = Text::from("\n".to_owned() + desc.value.as_str())
+                .style(theme.description);
             text.extend(description);
         }
 
-        let item = Paragraph::new(text).bg(bg).wrap(Wrap { trim: true });
+        let item = Paragraph::new(text).style(item_style).wrap(Wrap { trim: true });
         let mut item_area = area.clone();
 
         item_area.height -= 1;
@@ -133,11 +213,14 @@ impl<'a> StatefulWidget for SearchResultItem<'a> {
     }
 }
 
-pub struct SearchResultList<'bins> {
+pub struct SearchResultList<'bins, 'areas> {
     pub binary_list: &'bins BinaryListState,
+    pub item_areas: &'areas mut Vec<Rect>,
+    pub query: &'bins Query,
+    pub show_descriptions: bool,
 }
 
-impl<'a> Widget for SearchResultList<'a> {
+impl<'a, 'b> Widget for SearchResultList<'a, 'b> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let selected = self.binary_list.selected;
         let binaries = (&self.binary_list.binaries)
@@ -151,9 +234,24 @@ impl<'a> Widget for SearchResultList<'a> {
             let readable_binary = &binary.read().unwrap();
 
             let name = &readable_binary.name;
-            let description = readable_binary.get_description();
-            
-            let item = SearchResultItem { name, description };
+            let marker = readable_binary.get_marker();
+            let app_name = readable_binary.apps
+                .first()
+                .map(|app| app.name.as_str());
+            let description = match self.show_descriptions {
+                true => readable_binary.get_description(),
+                false => None,
+            };
+
+            let item = SearchResultItem {
+                name,
+                highlights: self.query.get_highlights(name),
+                marked: self.binary_list.is_marked(binary),
+                marker,
+                app_name,
+                aliases: &readable_binary.aliases,
+                description,
+            };
 
             let item_height = item.calculate_height(&area);
             let mut item_area = area.clone();
@@ -161,10 +259,11 @@ impl<'a> Widget for SearchResultList<'a> {
             item_area.y += height_offset;
             item_area.height = item_height.min(max_y - item_area.y);
             height_offset += item_area.height;
+            self.item_areas.push(item_area);
 
             let is_last = height_offset > area.height - 1;
 
-            let mut order = if i == selected.to_owned() as usize {
+            let mut order = if i == 0 {
                 SearchResultItemOrder::Selected
             } else if is_last {
                 SearchResultItemOrder::Last
@@ -181,24 +280,236 @@ impl<'a> Widget for SearchResultList<'a> {
     }
 }
 
-pub struct SearchResult<'bins> {
+pub struct SearchResult<'bins, 'areas> {
     pub binary_list: Option<&'bins BinaryListState>,
+    pub item_areas: &'areas mut Vec<Rect>,
+    pub query: &'bins Query,
+    pub show_descriptions: bool,
 }
 
-impl<'a> Widget for SearchResult<'a> {
+impl<'a, 'b> Widget for SearchResult<'a, 'b> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let result_block = Block::bordered().border_type(BorderType::Rounded);
+        self.item_areas.clear();
 
-        (&result_block).render(area, buf);
+        let mut result_block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(get_theme().border);
 
         let binary_list = match self.binary_list {
             Some(v) => v,
-            _ => return,
+            _ => {
+                result_block.render(area, buf);
+                return;
+            }
+        };
+
+        let count_text = match binary_list.marked.len() {
+            0 => format!(
+                " {}/{} ",
+                binary_list.binaries.len(),
+                binary_list.binaries.total,
+            ),
+            marked => format!(
+                " {}/{} ({} marked) ",
+                binary_list.binaries.len(),
+                binary_list.binaries.total,
+                marked,
+            ),
         };
 
+        result_block = result_block
+            .title_bottom(Line::styled(count_text, get_theme().status).right_aligned());
+
+        (&result_block).render(area, buf);
+
         let list_area = result_block.inner(area);
-        let list = SearchResultList { binary_list };
+        let list = SearchResultList {
+            binary_list,
+            item_areas: self.item_areas,
+            query: self.query,
+            show_descriptions: self.show_descriptions,
+        };
 
         list.render(list_area, buf);
     }
 }
+
+pub struct DetailsPanel<'bin> {
+    pub binaries: &'bin [&'bin Binary],
+    pub scroll: u16,
+}
+
+impl<'a> DetailsPanel<'a> {
+    fn get_line<'b>(label: &'b str, value: String) -> Line<'b> {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", label), get_theme().secondary),
+            Span::raw(value),
+        ])
+    }
+
+    fn get_binary_lines(binary: &Binary) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+
+        match binary.kind.marker() {
+            Some(marker) => lines.push(Self::get_line("Kind", marker.to_owned())),
+            None => lines.push(Self::get_line("Path", binary.path.display().to_string())),
+        }
+
+        if let Some(info) = &binary.file_info {
+            if let Some(target) = &info.symlink_target {
+                lines.push(Self::get_line("Target", target.display().to_string()));
+            }
+
+            lines.push(Self::get_line("Type", info.file_type.to_string()));
+        }
+
+        if let Some(details) = &binary.details {
+            if let Some(interpreter) = &details.interpreter {
+                lines.push(Self::get_line("Interp", interpreter.clone()));
+            }
+
+            lines.push(Self::get_line("Size", details.get_size()));
+
+            if let Some(modified) = details.get_modified() {
+                lines.push(Self::get_line("Modified", modified));
+            }
+
+            lines.push(Self::get_line("Mode", details.get_permissions()));
+            lines.push(Self::get_line("Owner", details.get_owner()));
+        }
+
+        if let Some(package) = binary.file_info.as_ref().and_then(|info| info.package.as_ref()) {
+            lines.push(Self::get_line("Package", package.to_string()));
+        }
+
+        if let Some(details) = &binary.details {
+            for (i, path) in details.shadowed.iter().enumerate() {
+                let label = if i == 0 { "Shadows" } else { "" };
+                lines.push(Self::get_line(label, path.display().to_string()));
+            }
+
+            if let Some(manpath) = &binary.manpath {
+                lines.push(Self::get_line("Manpage", manpath.display().to_string()));
+            }
+
+            if !details.sections.is_empty() {
+                lines.push(Self::get_line("Section", details.sections.join(", ")));
+            }
+        }
+
+        if !binary.aliases.is_empty() {
+            lines.push(Self::get_line("Aliases", binary.aliases.join(", ")));
+        }
+
+        if let Some(multi_call) = &binary.multi_call {
+            lines.push(Self::get_line("Applet", format!("of {}", multi_call)));
+        }
+
+        if let Some(hint) = binary.get_path_hint() {
+            lines.push(Self::get_line("Reach", hint));
+        }
+
+        lines
+    }
+}
+
+impl<'a> Widget for DetailsPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match self.binaries.len() {
+            0 | 1 => " Details ".to_owned(),
+            count => format!(" Details ({}) ", count),
+        };
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(get_theme().border)
+            .padding(Padding::horizontal(1))
+            .title(title);
+
+        let mut lines = vec![];
+
+        for binary in self.binaries {
+            if self.binaries.len() > 1 {
+                if !lines.is_empty() {
+                    lines.push(Line::default());
+                }
+
+                lines.push(Line::styled(binary.name.clone(), get_theme().name));
+            }
+
+            lines.extend(Self::get_binary_lines(binary));
+        }
+
+        let scroll = self.scroll.min(lines.len().saturating_sub(1) as u16);
+
+        let details = Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll, 0))
+            .wrap(Wrap { trim: true });
+
+        details.render(area, buf);
+    }
+}
+
+const STATUS_SEPARATOR: &str = " · ";
+
+pub struct StatusLine {
+    pub indexed: Option<usize>,
+    pub matched: usize,
+    pub selected: Option<(usize, usize)>,
+    pub scoring: Scoring,
+    pub latency: Option<Duration>,
+    pub loading: bool,
+    pub tick_rate: Option<u32>,
+}
+
+impl StatusLine {
+    fn join(parts: Vec<String>) -> Line<'static> {
+        Line::styled(format!(" {} ", parts.join(STATUS_SEPARATOR)), get_theme().status)
+    }
+
+    fn get_counts(&self) -> Line<'static> {
+        let mut parts = vec![];
+
+        if let Some(indexed) = self.indexed {
+            parts.push(format!("{} indexed", indexed));
+        }
+
+        parts.push(format!("{} matched", self.matched));
+
+        if let Some((selected, count)) = self.selected {
+            parts.push(format!("{}/{}", selected + 1, count));
+        }
+
+        Self::join(parts)
+    }
+
+    fn get_diagnostics(&self) -> Line<'static> {
+        let mut parts = vec![];
+
+        if self.loading {
+            parts.push("loading descriptions".to_owned());
+        }
+
+        if let Some(value) = self.scoring.to_possible_value() {
+            parts.push(value.get_name().to_owned());
+        }
+
+        if let Some(latency) = self.latency {
+            parts.push(format!("{} ms", latency.as_millis()));
+        }
+
+        if let Some(tick_rate) = self.tick_rate {
+            parts.push(format!("{} tps", tick_rate));
+        }
+
+        Self::join(parts).right_aligned()
+    }
+}
+
+impl Widget for StatusLine {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.get_counts().render(area, buf);
+        self.get_diagnostics().render(area, buf);
+    }
+}
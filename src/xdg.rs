@@ -0,0 +1,71 @@
+use std::{
+    env,
+    path::PathBuf,
+};
+
+const DEFAULT_DATA_DIRS: &str = "/usr/local/share:/usr/share";
+
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    let dir = env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from);
+
+    match dir {
+        Some(v) if v.is_absolute() => Some(v),
+        _ => home_dir().map(|home| home.join(fallback)),
+    }
+}
+
+pub fn data_home() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+pub fn data_dirs() -> Vec<PathBuf> {
+    let dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or(DEFAULT_DATA_DIRS.to_owned());
+
+    data_home()
+        .into_iter()
+        .chain(env::split_paths(&dirs).filter(|dir| dir.is_absolute()))
+        .collect()
+}
+
+pub fn system_data_dirs() -> Vec<PathBuf> {
+    env::split_paths(DEFAULT_DATA_DIRS).collect()
+}
+
+pub fn config_home() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+pub fn sb_config_file(name: &str) -> Option<PathBuf> {
+    config_home().map(|dir| dir.join("sb").join(name))
+}
+
+pub fn state_home() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+pub fn sb_state_file(name: &str) -> Option<PathBuf> {
+    state_home().map(|dir| dir.join("sb").join(name))
+}
+
+pub fn sb_runtime_file(name: &str) -> Option<PathBuf> {
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .filter(|v| v.is_absolute());
+
+    match runtime_dir {
+        Some(dir) => Some(dir.join("sb").join(name)),
+        None => sb_state_file(name),
+    }
+}